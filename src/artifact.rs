@@ -0,0 +1,333 @@
+use std::fs;
+use std::path::Path;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+
+use crate::content;
+use crate::manifest::SkillManifest;
+use crate::skill::{Skill, SkillMetadata};
+
+/// 扫描结果的类别：Agent Skill 目录或各工具的规则/指令文件
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactKind {
+    /// 含 `SKILL.md` 的目录
+    #[default]
+    Skill,
+    /// Cursor 规则文件 `.cursor/rules/*.mdc`
+    CursorRule,
+    /// `AGENTS.md`
+    AgentsMd,
+    /// Claude Code 记忆文件 `CLAUDE.md`
+    ClaudeMd,
+    /// `.github/copilot-instructions.md`
+    CopilotInstructions,
+    /// `.windsurfrules`
+    WindsurfRules,
+    /// Claude Code 斜杠命令 `.claude/commands/*.md`
+    ClaudeCommand,
+    /// Claude Code 子代理 `.claude/agents/*.md`
+    ClaudeAgent,
+}
+
+impl ArtifactKind {
+    /// 可以在交互模式与 `delete` 命令中浏览、删除的类别
+    pub const DELETABLE: &'static [ArtifactKind] =
+        &[ArtifactKind::Skill, ArtifactKind::ClaudeCommand, ArtifactKind::ClaudeAgent];
+
+    /// 与 `--kind` 及 JSON 输出一致的标签
+    pub fn label(self) -> &'static str {
+        match self {
+            ArtifactKind::Skill => "skill",
+            ArtifactKind::CursorRule => "cursor-rule",
+            ArtifactKind::AgentsMd => "agents-md",
+            ArtifactKind::ClaudeMd => "claude-md",
+            ArtifactKind::CopilotInstructions => "copilot-instructions",
+            ArtifactKind::WindsurfRules => "windsurf-rules",
+            ArtifactKind::ClaudeCommand => "claude-command",
+            ArtifactKind::ClaudeAgent => "claude-agent",
+        }
+    }
+
+    /// 该类别的提取器
+    pub fn extractor(self) -> &'static dyn ArtifactExtractor {
+        match self {
+            ArtifactKind::Skill => &SkillDirectoryExtractor,
+            ArtifactKind::CursorRule => &CursorRuleExtractor,
+            ArtifactKind::AgentsMd => &InstructionFileExtractor { file_name: "AGENTS.md" },
+            ArtifactKind::ClaudeMd => &InstructionFileExtractor { file_name: "CLAUDE.md" },
+            ArtifactKind::CopilotInstructions => &InstructionFileExtractor {
+                file_name: "copilot-instructions.md",
+            },
+            ArtifactKind::WindsurfRules => &InstructionFileExtractor { file_name: ".windsurfrules" },
+            ArtifactKind::ClaudeCommand | ArtifactKind::ClaudeAgent => &ClaudeExtensionExtractor,
+        }
+    }
+
+    /// 扫描根目录本身是否存在：skill 根目录必须是目录，其他类别可以是单个文件或包含规则文件的目录
+    pub fn root_exists(self, root_path: &Path) -> bool {
+        match self {
+            ArtifactKind::Skill => root_path.is_dir(),
+            _ => root_path.exists(),
+        }
+    }
+}
+
+/// 一类 artifact 的识别与元数据提取规则
+pub trait ArtifactExtractor: Sync {
+    /// 路径是否为该类 artifact（skill 为目录，其余为文件）
+    fn is_artifact(&self, path: &Path) -> bool;
+
+    /// 在列表中显示的名称
+    fn artifact_name(&self, path: &Path) -> Option<String> {
+        Some(path.file_name()?.to_string_lossy().to_string())
+    }
+
+    /// 读取描述、清单与内容哈希等元数据
+    fn read_metadata(&self, path: &Path) -> SkillMetadata;
+}
+
+/// Agent Skill 目录
+struct SkillDirectoryExtractor;
+
+impl ArtifactExtractor for SkillDirectoryExtractor {
+    fn is_artifact(&self, path: &Path) -> bool {
+        path.join("SKILL.md").is_file()
+    }
+
+    fn read_metadata(&self, path: &Path) -> SkillMetadata {
+        SkillMetadata::read(path)
+    }
+}
+
+/// Cursor `.mdc` 规则：frontmatter 中的 `description`、`globs` 与 `alwaysApply`
+struct CursorRuleExtractor;
+
+impl ArtifactExtractor for CursorRuleExtractor {
+    fn is_artifact(&self, path: &Path) -> bool {
+        path.is_file() && path.extension().is_some_and(|extension| extension == "mdc")
+    }
+
+    fn artifact_name(&self, path: &Path) -> Option<String> {
+        Some(path.file_stem()?.to_string_lossy().to_string())
+    }
+
+    fn read_metadata(&self, path: &Path) -> SkillMetadata {
+        let content = fs::read_to_string(path).ok();
+        let manifest = content
+            .as_deref()
+            .and_then(|content| SkillManifest::parse(content).ok());
+        let rule_activation = manifest.as_ref().map(|manifest| RuleActivation::from_frontmatter(&manifest.raw));
+        SkillMetadata {
+            description: content
+                .as_deref()
+                .and_then(|content| Skill::extract_description(content, manifest.as_ref())),
+            manifest: None,
+            rule: rule_activation,
+            extension: None,
+            content_hash: content::hash_directory(path).ok(),
+        }
+    }
+}
+
+/// 固定文件名的 Markdown / 纯文本指令文件；描述取正文第一个非空、非标题行
+struct InstructionFileExtractor {
+    file_name: &'static str,
+}
+
+impl ArtifactExtractor for InstructionFileExtractor {
+    fn is_artifact(&self, path: &Path) -> bool {
+        path.is_file() && path.file_name().is_some_and(|file_name| file_name == self.file_name)
+    }
+
+    fn read_metadata(&self, path: &Path) -> SkillMetadata {
+        let content = fs::read_to_string(path).ok();
+        SkillMetadata {
+            description: content
+                .as_deref()
+                .and_then(|content| Skill::extract_description(content, None)),
+            manifest: None,
+            rule: None,
+            extension: None,
+            content_hash: content::hash_directory(path).ok(),
+        }
+    }
+}
+
+/// Claude Code 的命令与子代理：单个 `.md` 文件，frontmatter 中的 `description`、`allowed-tools`、`model`、`tools`
+struct ClaudeExtensionExtractor;
+
+impl ArtifactExtractor for ClaudeExtensionExtractor {
+    fn is_artifact(&self, path: &Path) -> bool {
+        path.is_file() && path.extension().is_some_and(|extension| extension == "md")
+    }
+
+    fn artifact_name(&self, path: &Path) -> Option<String> {
+        Some(path.file_stem()?.to_string_lossy().to_string())
+    }
+
+    fn read_metadata(&self, path: &Path) -> SkillMetadata {
+        let content = fs::read_to_string(path).ok();
+        let manifest = content
+            .as_deref()
+            .and_then(|content| SkillManifest::parse(content).ok());
+        SkillMetadata {
+            description: content
+                .as_deref()
+                .and_then(|content| Skill::extract_description(content, manifest.as_ref())),
+            manifest: None,
+            rule: None,
+            extension: manifest.as_ref().map(|manifest| ClaudeExtension::from_frontmatter(&manifest.raw)),
+            content_hash: content::hash_directory(path).ok(),
+        }
+    }
+}
+
+/// 逗号分隔字符串或 YAML 字符串列表
+fn frontmatter_list(frontmatter: &serde_yaml::Mapping, key: &str) -> Vec<String> {
+    match frontmatter.get(key) {
+        Some(Value::String(items)) => items
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Some(Value::Sequence(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 规则的生效方式（Cursor `.mdc` frontmatter）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleActivation {
+    /// 匹配这些文件时自动附加，兼容逗号分隔字符串与 YAML 列表
+    pub globs: Vec<String>,
+    /// 是否总是生效
+    pub always_apply: bool,
+}
+
+impl RuleActivation {
+    fn from_frontmatter(frontmatter: &serde_yaml::Mapping) -> Self {
+        Self {
+            globs: frontmatter_list(frontmatter, "globs"),
+            always_apply: frontmatter
+                .get("alwaysApply")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        }
+    }
+
+    /// 在列表中显示的生效方式
+    pub fn summary(&self) -> String {
+        if self.always_apply {
+            "总是生效".to_string()
+        } else if self.globs.is_empty() {
+            "按需引用".to_string()
+        } else {
+            format!("匹配 {}", self.globs.join(", "))
+        }
+    }
+}
+
+/// Claude Code 命令与子代理的 frontmatter 设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaudeExtension {
+    /// 命令的 `allowed-tools`
+    pub allowed_tools: Vec<String>,
+    /// `model`
+    pub model: Option<String>,
+    /// 子代理的 `tools`
+    pub tools: Vec<String>,
+}
+
+impl ClaudeExtension {
+    fn from_frontmatter(frontmatter: &serde_yaml::Mapping) -> Self {
+        Self {
+            allowed_tools: frontmatter_list(frontmatter, "allowed-tools"),
+            model: frontmatter
+                .get("model")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            tools: frontmatter_list(frontmatter, "tools"),
+        }
+    }
+
+    /// 在列表中显示的设置摘要；没有任何设置时为 None
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(model) = &self.model {
+            parts.push(format!("模型 {}", model));
+        }
+        if !self.tools.is_empty() {
+            parts.push(format!("工具 {}", self.tools.join(", ")));
+        }
+        if !self.allowed_tools.is_empty() {
+            parts.push(format!("允许 {}", self.allowed_tools.join(", ")));
+        }
+        (!parts.is_empty()).then(|| parts.join(" · "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+
+    #[test]
+    fn extractors_read_rule_activation_and_instruction_descriptions() {
+        let temp_directory_path = create_temp_directory("artifact");
+        let rule_file_path = temp_directory_path.join("react.mdc");
+        fs::write(
+            &rule_file_path,
+            "---\ndescription: React 约定\nglobs: src/**/*.tsx, src/**/*.jsx\nalwaysApply: false\n---\n使用函数组件\n",
+        )
+        .expect("failed to write rule");
+        let agents_file_path = temp_directory_path.join("AGENTS.md");
+        fs::write(&agents_file_path, "# 仓库说明\n\n提交前运行 cargo test\n").expect("failed to write AGENTS.md");
+
+        let cursor_rule_extractor = ArtifactKind::CursorRule.extractor();
+        assert!(cursor_rule_extractor.is_artifact(&rule_file_path));
+        assert!(!cursor_rule_extractor.is_artifact(&agents_file_path));
+        assert_eq!(cursor_rule_extractor.artifact_name(&rule_file_path).as_deref(), Some("react"));
+        let rule_metadata = cursor_rule_extractor.read_metadata(&rule_file_path);
+        assert_eq!(rule_metadata.description.as_deref(), Some("React 约定"));
+        assert_eq!(
+            rule_metadata.rule,
+            Some(RuleActivation {
+                globs: vec!["src/**/*.tsx".to_string(), "src/**/*.jsx".to_string()],
+                always_apply: false,
+            })
+        );
+
+        let agents_extractor = ArtifactKind::AgentsMd.extractor();
+        assert!(agents_extractor.is_artifact(&agents_file_path));
+        let agents_metadata = agents_extractor.read_metadata(&agents_file_path);
+        assert_eq!(agents_metadata.description.as_deref(), Some("提交前运行 cargo test"));
+        assert!(agents_metadata.rule.is_none());
+        assert!(agents_metadata.content_hash.is_some());
+
+        let agent_file_path = temp_directory_path.join("reviewer.md");
+        fs::write(
+            &agent_file_path,
+            "---\nname: reviewer\ndescription: 审查改动\ntools: Read, Grep\nmodel: sonnet\n---\n你是代码审查者\n",
+        )
+        .expect("failed to write agent");
+        let agent_extractor = ArtifactKind::ClaudeAgent.extractor();
+        assert!(agent_extractor.is_artifact(&agent_file_path));
+        assert!(!agent_extractor.is_artifact(&rule_file_path));
+        assert_eq!(agent_extractor.artifact_name(&agent_file_path).as_deref(), Some("reviewer"));
+        let agent_metadata = agent_extractor.read_metadata(&agent_file_path);
+        assert_eq!(agent_metadata.description.as_deref(), Some("审查改动"));
+        let extension = agent_metadata.extension.expect("agent frontmatter should be parsed");
+        assert_eq!(extension.model.as_deref(), Some("sonnet"));
+        assert_eq!(extension.tools, vec!["Read".to_string(), "Grep".to_string()]);
+        assert_eq!(extension.summary().as_deref(), Some("模型 sonnet · 工具 Read, Grep"));
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::UNIX_EPOCH;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::artifact::ArtifactKind;
+use crate::skill::SkillMetadata;
+
+/// 缓存文件格式版本，结构发生不兼容变更时递增
+const CACHE_FORMAT_VERSION: u32 = 3;
+/// 缓存文件名（位于 `<cache>/skills-scanner/` 下）
+const CACHE_FILE_NAME: &str = "scan-cache.json";
+
+/// skill 目录的修改指纹：目录树中最晚的修改时间、文件总大小与条目数
+///
+/// 只读取文件元数据，不读取内容；增删、改名或修改任一文件都会改变指纹。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectoryStamp {
+    pub modified_secs: u64,
+    pub modified_nanos: u32,
+    pub total_size: u64,
+    pub entry_count: u64,
+}
+
+impl DirectoryStamp {
+    /// 读取目录指纹（跟随符号链接）
+    pub fn read(directory_path: &Path) -> io::Result<Self> {
+        let mut latest_modified_time = UNIX_EPOCH;
+        let mut total_size = 0;
+        let mut entry_count = 0;
+        for entry in WalkDir::new(directory_path).follow_links(true) {
+            let metadata = entry.and_then(|entry| entry.metadata()).map_err(io::Error::other)?;
+            latest_modified_time = latest_modified_time.max(metadata.modified()?);
+            if metadata.is_file() {
+                total_size += metadata.len();
+            }
+            entry_count += 1;
+        }
+        let since_epoch = latest_modified_time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self {
+            modified_secs: since_epoch.as_secs(),
+            modified_nanos: since_epoch.subsec_nanos(),
+            total_size,
+            entry_count,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    stamp: DirectoryStamp,
+    metadata: SkillMetadata,
+}
+
+/// 磁盘上的缓存文件；条目逐个序列化，单个条目无法表示为 JSON 时只跳过该条目
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    generator_version: String,
+    entries: BTreeMap<PathBuf, serde_json::Value>,
+}
+
+/// 扫描缓存：按 skill 目录（或规则文件）路径保存解析后的元数据，指纹未变时跳过读取与哈希
+///
+/// 缓存文件由其他版本生成或已损坏时视为空缓存；写入失败不影响扫描结果。
+pub struct ScanCache {
+    file_path: PathBuf,
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+    is_dirty: AtomicBool,
+}
+
+impl ScanCache {
+    /// 打开指定位置的缓存文件
+    pub fn open(file_path: PathBuf) -> Self {
+        let entries = fs::read(&file_path)
+            .ok()
+            .and_then(|content| serde_json::from_slice::<CacheFile>(&content).ok())
+            .filter(|cache_file| {
+                cache_file.version == CACHE_FORMAT_VERSION
+                    && cache_file.generator_version == env!("CARGO_PKG_VERSION")
+            })
+            .map(|cache_file| {
+                cache_file
+                    .entries
+                    .into_iter()
+                    .filter_map(|(path, value)| Some((path, serde_json::from_value(value).ok()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            file_path,
+            entries: Mutex::new(entries),
+            is_dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// 打开默认位置 `<cache>/skills-scanner/scan-cache.json` 的缓存
+    pub fn open_default() -> Option<Self> {
+        default_cache_file_path().map(Self::open)
+    }
+
+    /// 缓存文件路径
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
+    /// 获取 skill 目录或规则文件的元数据：指纹与缓存一致时直接返回缓存，否则按类别重新读取并更新缓存
+    pub fn load_metadata(&self, skill_directory_path: &Path, artifact_kind: ArtifactKind) -> SkillMetadata {
+        let extractor = artifact_kind.extractor();
+        let Ok(stamp) = DirectoryStamp::read(skill_directory_path) else {
+            return extractor.read_metadata(skill_directory_path);
+        };
+        if let Some(cache_entry) = self.lock_entries().get(skill_directory_path) {
+            if cache_entry.stamp == stamp {
+                return cache_entry.metadata.clone();
+            }
+        }
+
+        let metadata = extractor.read_metadata(skill_directory_path);
+        self.lock_entries().insert(
+            skill_directory_path.to_path_buf(),
+            CacheEntry {
+                stamp,
+                metadata: metadata.clone(),
+            },
+        );
+        self.is_dirty.store(true, Ordering::Relaxed);
+        metadata
+    }
+
+    /// 有更新时写回磁盘，同时移除已不存在的 skill 目录与规则文件
+    pub fn save(&self) -> io::Result<()> {
+        if !self.is_dirty.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let mut entries = self.lock_entries();
+        entries.retain(|skill_directory_path, _| skill_directory_path.exists());
+        let cache_file = CacheFile {
+            version: CACHE_FORMAT_VERSION,
+            generator_version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: entries
+                .iter()
+                .filter_map(|(path, cache_entry)| Some((path.clone(), serde_json::to_value(cache_entry).ok()?)))
+                .collect(),
+        };
+        drop(entries);
+
+        if let Some(parent_directory_path) = self.file_path.parent() {
+            fs::create_dir_all(parent_directory_path)?;
+        }
+        // 先写临时文件再替换，避免并发运行时读到半个文件
+        let temporary_file_path = self.file_path.with_extension(format!("tmp-{}", std::process::id()));
+        fs::write(&temporary_file_path, serde_json::to_vec(&cache_file)?)?;
+        fs::rename(&temporary_file_path, &self.file_path)?;
+        self.is_dirty.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn lock_entries(&self) -> MutexGuard<'_, HashMap<PathBuf, CacheEntry>> {
+        // 持锁线程 panic 时缓存内容仍然可用
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 默认缓存文件位置
+pub fn default_cache_file_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|cache_directory_path| cache_directory_path.join("skills-scanner").join(CACHE_FILE_NAME))
+}
+
+/// 删除缓存文件；文件不存在时返回 `Ok(false)`
+pub fn clear_cache_file(file_path: &Path) -> io::Result<bool> {
+    match fs::remove_file(file_path) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+
+    #[test]
+    fn cache_reuses_unchanged_metadata_and_refreshes_changed_skills() {
+        let temp_directory_path = create_temp_directory("scan-cache");
+        let cache_file_path = temp_directory_path.join("cache").join(CACHE_FILE_NAME);
+        let skill_directory_path = temp_directory_path.join("pdf");
+        fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+        fs::write(skill_directory_path.join("SKILL.md"), "---\nname: pdf\ndescription: v1\n---\n")
+            .expect("failed to write SKILL.md");
+
+        let scan_cache = ScanCache::open(cache_file_path.clone());
+        assert_eq!(scan_cache.load_metadata(&skill_directory_path, ArtifactKind::Skill).description.as_deref(), Some("v1"));
+        scan_cache.save().expect("failed to save cache");
+
+        // 重新打开后命中缓存：篡改缓存中的描述可以观察到未重新读取
+        let mut cache_file: CacheFile =
+            serde_json::from_slice(&fs::read(&cache_file_path).unwrap()).expect("cache file should parse");
+        let cached_entry = cache_file.entries.get_mut(&skill_directory_path).expect("entry should exist");
+        cached_entry["metadata"]["description"] = serde_json::Value::from("cached");
+        fs::write(&cache_file_path, serde_json::to_vec(&cache_file).unwrap()).unwrap();
+        let scan_cache = ScanCache::open(cache_file_path.clone());
+        assert_eq!(scan_cache.load_metadata(&skill_directory_path, ArtifactKind::Skill).description.as_deref(), Some("cached"));
+
+        // 内容变化后指纹不同，重新读取
+        fs::write(skill_directory_path.join("scripts.sh"), "echo hi").expect("failed to add file");
+        let metadata = scan_cache.load_metadata(&skill_directory_path, ArtifactKind::Skill);
+        assert_eq!(metadata.description.as_deref(), Some("v1"));
+        assert!(metadata.content_hash.is_some());
+
+        assert!(clear_cache_file(&cache_file_path).expect("failed to clear cache"));
+        assert!(!clear_cache_file(&cache_file_path).expect("clearing twice should succeed"));
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
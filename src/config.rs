@@ -0,0 +1,355 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+
+use crate::output::OutputFormat;
+use skills_scanner::registry::{app_config_file_path, expand_path_variables};
+use skills_scanner::scanner::{self, ScanOptions};
+use skills_scanner::artifact::ArtifactKind;
+use skills_scanner::skill::SortKey;
+
+/// 全局配置文件名（位于 `<config>/skills-scanner/` 下）
+const GLOBAL_CONFIG_FILE_NAME: &str = "config.toml";
+/// 项目配置文件名（从当前目录向上查找到 git 根）
+const PROJECT_CONFIG_FILE_NAME: &str = ".skills-scanner.toml";
+
+/// 配置文件中的一层设置（顶层或某个 profile）
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettingsLayer {
+    /// 额外扫描目录
+    #[serde(default)]
+    paths: Vec<String>,
+    /// 排除的工具名称
+    #[serde(default)]
+    exclude_tools: Vec<String>,
+    /// 排除的路径
+    #[serde(default)]
+    exclude_paths: Vec<String>,
+    /// 默认输出格式
+    format: Option<OutputFormat>,
+    /// 默认排序方式
+    sort: Option<SortKey>,
+    /// 并行扫描的线程数
+    threads: Option<NonZeroUsize>,
+    /// 查找 skill 目录的最大深度
+    max_depth: Option<usize>,
+    /// 查找时跳过的目录名
+    #[serde(default)]
+    ignore_dirs: Vec<String>,
+    /// 只查找这些类别
+    kinds: Option<Vec<ArtifactKind>>,
+}
+
+/// 配置文件结构：顶层设置加上若干命名 profile
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    exclude_tools: Vec<String>,
+    #[serde(default)]
+    exclude_paths: Vec<String>,
+    format: Option<OutputFormat>,
+    sort: Option<SortKey>,
+    threads: Option<NonZeroUsize>,
+    max_depth: Option<usize>,
+    #[serde(default)]
+    ignore_dirs: Vec<String>,
+    kinds: Option<Vec<ArtifactKind>>,
+    #[serde(default)]
+    profiles: BTreeMap<String, SettingsLayer>,
+}
+
+impl ConfigFile {
+    /// 拆分为顶层设置与 profile 表
+    fn into_layers(self) -> (SettingsLayer, BTreeMap<String, SettingsLayer>) {
+        let base_layer = SettingsLayer {
+            paths: self.paths,
+            exclude_tools: self.exclude_tools,
+            exclude_paths: self.exclude_paths,
+            format: self.format,
+            sort: self.sort,
+            threads: self.threads,
+            max_depth: self.max_depth,
+            ignore_dirs: self.ignore_dirs,
+            kinds: self.kinds,
+        };
+        (base_layer, self.profiles)
+    }
+}
+
+/// 加载配置失败的原因
+#[derive(Debug)]
+pub enum ConfigError {
+    /// 读取文件失败
+    Io { path: PathBuf, source: io::Error },
+    /// TOML 解析失败
+    Parse { path: PathBuf, source: toml::de::Error },
+    /// 路径中引用了未定义的环境变量
+    UndefinedVariable { path: PathBuf, value: String },
+    /// 所有配置文件中都找不到指定的 profile
+    UnknownProfile { name: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "无法读取 {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, source } => {
+                write!(f, "无法解析 {}: {}", path.display(), source)
+            }
+            ConfigError::UndefinedVariable { path, value } => write!(
+                f,
+                "{} 中的路径 `{}` 引用了未定义的环境变量",
+                path.display(),
+                value
+            ),
+            ConfigError::UnknownProfile { name } => {
+                write!(f, "未在任何配置文件中找到 profile `{}`", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// 合并后的最终设置
+///
+/// 合并顺序：内置默认值 → 全局配置 → 项目 `.skills-scanner.toml` → 命令行参数。
+/// 每个配置文件中先应用顶层设置，再应用选中的 profile。
+/// 列表类设置（目录、排除项）逐层追加，单值设置（格式、排序）逐层覆盖。
+#[derive(Debug, Clone)]
+pub struct Settings {
+    /// 额外扫描目录
+    pub paths: Vec<PathBuf>,
+    /// 扫描选项
+    pub scan_options: ScanOptions,
+    /// 输出格式
+    pub format: OutputFormat,
+    /// 排序方式
+    pub sort: SortKey,
+    /// 是否使用扫描缓存
+    pub use_cache: bool,
+    /// 参与合并的配置文件
+    pub sources: Vec<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            paths: Vec::new(),
+            scan_options: ScanOptions::default(),
+            format: OutputFormat::Text,
+            sort: SortKey::Scan,
+            use_cache: true,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// 命令行中可覆盖配置的参数
+#[derive(Debug, Default)]
+pub struct CliOverrides {
+    pub profile: Option<String>,
+    pub paths: Vec<PathBuf>,
+    pub format: Option<OutputFormat>,
+    pub sort: Option<SortKey>,
+    pub threads: Option<NonZeroUsize>,
+    pub max_depth: Option<usize>,
+    pub ignore_dirs: Vec<String>,
+    pub kinds: Vec<ArtifactKind>,
+    pub no_cache: bool,
+}
+
+impl Settings {
+    /// 从全局配置与项目配置加载，并应用命令行参数
+    pub fn load(overrides: CliOverrides) -> Result<Self, ConfigError> {
+        let mut config_paths = Vec::new();
+        if let Some(global_config_path) = app_config_file_path(GLOBAL_CONFIG_FILE_NAME) {
+            config_paths.push(global_config_path);
+        }
+        if let Some(project_config_path) = project_config_path() {
+            config_paths.push(project_config_path);
+        }
+
+        let mut settings = Self::default();
+        let mut is_profile_found = false;
+        for config_path in &config_paths {
+            let content = fs::read_to_string(config_path).map_err(|source| ConfigError::Io {
+                path: config_path.clone(),
+                source,
+            })?;
+            is_profile_found |=
+                settings.apply_file(&content, config_path, overrides.profile.as_deref())?;
+        }
+
+        if let Some(profile_name) = overrides.profile {
+            if !is_profile_found {
+                return Err(ConfigError::UnknownProfile { name: profile_name });
+            }
+        }
+
+        settings.paths.extend(overrides.paths);
+        if let Some(format) = overrides.format {
+            settings.format = format;
+        }
+        if let Some(sort) = overrides.sort {
+            settings.sort = sort;
+        }
+        if let Some(threads) = overrides.threads {
+            settings.scan_options.threads = Some(threads.get());
+        }
+        if let Some(max_depth) = overrides.max_depth {
+            settings.scan_options.max_depth = Some(max_depth);
+        }
+        settings.scan_options.ignore_directory_names.extend(overrides.ignore_dirs);
+        if !overrides.kinds.is_empty() {
+            settings.scan_options.kinds = overrides.kinds;
+        }
+        settings.use_cache = !overrides.no_cache;
+        Ok(settings)
+    }
+
+    /// 应用一个配置文件的内容；返回其中是否包含选中的 profile
+    fn apply_file(
+        &mut self,
+        content: &str,
+        config_path: &Path,
+        profile_name: Option<&str>,
+    ) -> Result<bool, ConfigError> {
+        let config_file: ConfigFile = toml::from_str(content).map_err(|source| ConfigError::Parse {
+            path: config_path.to_path_buf(),
+            source,
+        })?;
+        let (base_layer, mut profiles) = config_file.into_layers();
+
+        self.apply_layer(base_layer, config_path)?;
+        let profile_layer = profile_name.and_then(|name| profiles.remove(name));
+        let is_profile_found = profile_layer.is_some();
+        if let Some(profile_layer) = profile_layer {
+            self.apply_layer(profile_layer, config_path)?;
+        }
+
+        self.sources.push(config_path.to_path_buf());
+        Ok(is_profile_found)
+    }
+
+    fn apply_layer(&mut self, layer: SettingsLayer, config_path: &Path) -> Result<(), ConfigError> {
+        for raw_path in &layer.paths {
+            self.paths.push(resolve_config_path(raw_path, config_path)?);
+        }
+        for raw_path in &layer.exclude_paths {
+            self.scan_options
+                .exclude_paths
+                .push(resolve_config_path(raw_path, config_path)?);
+        }
+        self.scan_options.exclude_tools.extend(layer.exclude_tools);
+        if let Some(format) = layer.format {
+            self.format = format;
+        }
+        if let Some(sort) = layer.sort {
+            self.sort = sort;
+        }
+        if let Some(threads) = layer.threads {
+            self.scan_options.threads = Some(threads.get());
+        }
+        if let Some(max_depth) = layer.max_depth {
+            self.scan_options.max_depth = Some(max_depth);
+        }
+        self.scan_options.ignore_directory_names.extend(layer.ignore_dirs);
+        if let Some(kinds) = layer.kinds {
+            self.scan_options.kinds = kinds;
+        }
+        Ok(())
+    }
+}
+
+/// 展开配置中的路径；相对路径以配置文件所在目录为基准
+fn resolve_config_path(raw_path: &str, config_path: &Path) -> Result<PathBuf, ConfigError> {
+    let expanded_path = expand_path_variables(raw_path).map_err(|_| ConfigError::UndefinedVariable {
+        path: config_path.to_path_buf(),
+        value: raw_path.to_string(),
+    })?;
+    let expanded_path = PathBuf::from(expanded_path);
+    if expanded_path.is_absolute() {
+        return Ok(expanded_path);
+    }
+    let config_directory_path = config_path.parent().unwrap_or_else(|| Path::new("."));
+    Ok(config_directory_path.join(expanded_path))
+}
+
+/// 从当前目录向上查找最近的项目配置文件
+fn project_config_path() -> Option<PathBuf> {
+    scanner::workspace_search_paths()
+        .into_iter()
+        .map(|directory_path| directory_path.join(PROJECT_CONFIG_FILE_NAME))
+        .find(|config_path| config_path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLOBAL_CONFIG: &str = r#"
+        paths = ["/shared/skills"]
+        format = "json"
+
+        [profiles.work]
+        paths = ["work-skills"]
+        exclude_tools = ["Cursor"]
+        sort = "tool"
+
+        [profiles.oss]
+        exclude_paths = ["/oss/ignored"]
+    "#;
+
+    const PROJECT_CONFIG: &str = r#"
+        sort = "name"
+
+        [profiles.work]
+        format = "ndjson"
+    "#;
+
+    #[test]
+    fn apply_file_merges_layers_in_order() {
+        let mut settings = Settings::default();
+
+        let is_global_profile_found = settings
+            .apply_file(GLOBAL_CONFIG, Path::new("/home/me/.config/skills-scanner/config.toml"), Some("work"))
+            .expect("failed to apply global config");
+        let is_project_profile_found = settings
+            .apply_file(PROJECT_CONFIG, Path::new("/repo/.skills-scanner.toml"), Some("work"))
+            .expect("failed to apply project config");
+
+        assert!(is_global_profile_found && is_project_profile_found);
+        assert_eq!(
+            settings.paths,
+            vec![
+                PathBuf::from("/shared/skills"),
+                PathBuf::from("/home/me/.config/skills-scanner/work-skills"),
+            ]
+        );
+        assert_eq!(settings.scan_options.exclude_tools, vec!["Cursor".to_string()]);
+        assert!(settings.scan_options.exclude_paths.is_empty());
+        // 项目配置的顶层 sort 覆盖全局 profile，项目 profile 覆盖全局顶层 format
+        assert_eq!(settings.sort, SortKey::Name);
+        assert_eq!(settings.format, OutputFormat::Ndjson);
+        assert_eq!(settings.sources.len(), 2);
+    }
+
+    #[test]
+    fn apply_file_rejects_unknown_keys() {
+        let mut settings = Settings::default();
+        let result = settings.apply_file("exclude_tool = [\"Cursor\"]\n", Path::new("config.toml"), None);
+
+        assert!(matches!(result, Err(ConfigError::Parse { .. })));
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+/// 目录中每个文件的内容哈希，键为以 `/` 分隔的相对路径
+pub type FileHashes = BTreeMap<String, String>;
+
+/// 计算目录下所有文件的 SHA-256（跟随符号链接，按路径排序）
+pub fn hash_files(directory_path: &Path) -> io::Result<FileHashes> {
+    let mut file_hashes = FileHashes::new();
+    for entry in WalkDir::new(directory_path)
+        .follow_links(true)
+        .sort_by_file_name()
+    {
+        let entry = entry.map_err(io::Error::other)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let relative_path = entry
+            .path()
+            .strip_prefix(directory_path)
+            .unwrap_or(entry.path())
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        file_hashes.insert(relative_path, hash_file(entry.path())?);
+    }
+    Ok(file_hashes)
+}
+
+/// 由各文件哈希计算整个目录树的哈希；文件名与内容任一变化都会改变结果
+pub fn tree_hash(file_hashes: &FileHashes) -> String {
+    let mut hasher = Sha256::new();
+    for (relative_path, file_hash) in file_hashes {
+        hasher.update(relative_path.as_bytes());
+        hasher.update([0]);
+        hasher.update(file_hash.as_bytes());
+        hasher.update([b'\n']);
+    }
+    to_hex(&hasher.finalize())
+}
+
+/// 计算目录树哈希
+pub fn hash_directory(directory_path: &Path) -> io::Result<String> {
+    hash_files(directory_path).map(|file_hashes| tree_hash(&file_hashes))
+}
+
+/// 目录下所有文件中最晚的修改时间（跟随符号链接）；目录为空时取目录本身的修改时间
+pub fn last_modified(directory_path: &Path) -> io::Result<SystemTime> {
+    let mut latest_modified_time = std::fs::metadata(directory_path)?.modified()?;
+    for entry in WalkDir::new(directory_path).follow_links(true) {
+        let entry = entry.map_err(io::Error::other)?;
+        if entry.file_type().is_file() {
+            latest_modified_time = latest_modified_time.max(entry.metadata().map_err(io::Error::other)?.modified()?);
+        }
+    }
+    Ok(latest_modified_time)
+}
+
+fn hash_file(file_path: &Path) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(file_path)?, &mut hasher)?;
+    Ok(to_hex(&hasher.finalize()))
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// 两份目录之间的文件级差异
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FileDiff {
+    /// 仅存在于对比方的文件
+    pub added: Vec<String>,
+    /// 仅存在于基准方的文件
+    pub removed: Vec<String>,
+    /// 两边都存在但内容不同的文件
+    pub modified: Vec<String>,
+}
+
+impl FileDiff {
+    /// 比较基准与对比方的文件哈希
+    pub fn between(base_file_hashes: &FileHashes, other_file_hashes: &FileHashes) -> Self {
+        let mut file_diff = Self::default();
+        for (relative_path, base_file_hash) in base_file_hashes {
+            match other_file_hashes.get(relative_path) {
+                None => file_diff.removed.push(relative_path.clone()),
+                Some(other_file_hash) if other_file_hash != base_file_hash => {
+                    file_diff.modified.push(relative_path.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        for relative_path in other_file_hashes.keys() {
+            if !base_file_hashes.contains_key(relative_path) {
+                file_diff.added.push(relative_path.clone());
+            }
+        }
+        file_diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+    use std::fs;
+
+    fn write_tree(directory_path: &Path, files: &[(&str, &str)]) {
+        for (relative_path, content) in files {
+            let file_path = directory_path.join(relative_path);
+            fs::create_dir_all(file_path.parent().unwrap()).expect("failed to create parent directory");
+            fs::write(file_path, content).expect("failed to write file");
+        }
+    }
+
+    #[test]
+    fn tree_hash_and_diff_track_names_and_contents() {
+        let temp_directory_path = create_temp_directory("content-hash");
+        let first_path = temp_directory_path.join("first");
+        let second_path = temp_directory_path.join("second");
+        write_tree(&first_path, &[("SKILL.md", "# pdf"), ("scripts/run.sh", "echo 1")]);
+        write_tree(&second_path, &[("SKILL.md", "# pdf"), ("scripts/run.sh", "echo 1")]);
+
+        assert_eq!(
+            hash_directory(&first_path).unwrap(),
+            hash_directory(&second_path).unwrap()
+        );
+
+        write_tree(&second_path, &[("scripts/run.sh", "echo 2"), ("notes.md", "new")]);
+        fs::remove_file(second_path.join("SKILL.md")).unwrap();
+        let first_file_hashes = hash_files(&first_path).unwrap();
+        let second_file_hashes = hash_files(&second_path).unwrap();
+
+        assert_ne!(tree_hash(&first_file_hashes), tree_hash(&second_file_hashes));
+        assert_eq!(
+            FileDiff::between(&first_file_hashes, &second_file_hashes),
+            FileDiff {
+                added: vec!["notes.md".to_string()],
+                removed: vec!["SKILL.md".to_string()],
+                modified: vec!["scripts/run.sh".to_string()],
+            }
+        );
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+use glob::{MatchOptions, Pattern, PatternError};
+use serde::Serialize;
+
+use crate::fs_util;
+use crate::registry;
+use crate::skill::Skill;
+use crate::trash::DeleteMode;
+
+/// 名称与工具的匹配均不区分大小写
+const SELECTOR_MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// 非交互删除时用于筛选 skills 的条件
+///
+/// 同一类条件之间为“或”，不同类条件之间为“且”；某类条件为空时不参与筛选。
+#[derive(Debug, Default)]
+pub struct SkillSelector {
+    name_patterns: Vec<Pattern>,
+    tool_patterns: Vec<Pattern>,
+    path_prefixes: Vec<PathBuf>,
+}
+
+impl SkillSelector {
+    /// 由命令行参数构建；glob 语法错误时返回错误
+    pub fn new(
+        names: &[String],
+        tools: &[String],
+        path_prefixes: &[PathBuf],
+    ) -> Result<Self, PatternError> {
+        let compile = |patterns: &[String]| -> Result<Vec<Pattern>, PatternError> {
+            patterns.iter().map(|pattern| Pattern::new(pattern)).collect()
+        };
+        Ok(Self {
+            name_patterns: compile(names)?,
+            tool_patterns: compile(tools)?,
+            path_prefixes: path_prefixes.iter().map(|path| fs_util::absolutize(path)).collect(),
+        })
+    }
+
+    /// 判断 skill 是否满足全部条件
+    pub fn matches(&self, skill: &Skill) -> bool {
+        matches_any_pattern(&self.name_patterns, &skill.name)
+            && matches_any_tool_pattern(&self.tool_patterns, &skill.tool)
+            && (self.path_prefixes.is_empty() || {
+                let skill_path = fs_util::absolutize(&skill.path);
+                self.path_prefixes
+                    .iter()
+                    .any(|path_prefix| skill_path.starts_with(path_prefix))
+            })
+    }
+}
+
+/// 工具条件：含通配符时按 glob 匹配完整名称，否则匹配工具族名称或其中的完整单词
+/// （如 `codex` 匹配 `OpenAI Codex`，`cursor` 同时匹配 `Cursor` 与 `Cursor (Project)`）
+fn matches_any_tool_pattern(patterns: &[Pattern], tool_name: &str) -> bool {
+    let tool_family = registry::tool_family(tool_name).to_lowercase();
+    patterns.is_empty()
+        || patterns.iter().any(|pattern| {
+            let pattern_text = pattern.as_str();
+            if pattern_text.contains(['*', '?', '[']) {
+                return pattern.matches_with(tool_name, SELECTOR_MATCH_OPTIONS);
+            }
+            let query = pattern_text.to_lowercase();
+            tool_name.to_lowercase() == query
+                || tool_family == query
+                || tool_family.split_whitespace().any(|word| word == query)
+        })
+}
+
+fn matches_any_pattern(patterns: &[Pattern], value: &str) -> bool {
+    patterns.is_empty()
+        || patterns
+            .iter()
+            .any(|pattern| pattern.matches_with(value, SELECTOR_MATCH_OPTIONS))
+}
+
+/// 单个 skill 的删除结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeleteStatus {
+    /// dry-run：将会删除
+    Planned,
+    /// 已移入回收站
+    Trashed,
+    /// 已永久删除
+    Deleted,
+    /// 删除失败
+    Failed,
+}
+
+/// 删除结果记录，用于文本与 JSON 输出
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteOutcome {
+    pub name: String,
+    pub tool: String,
+    pub path: PathBuf,
+    pub status: DeleteStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeleteOutcome {
+    fn new(skill: &Skill, status: DeleteStatus) -> Self {
+        Self {
+            name: skill.name.clone(),
+            tool: skill.tool.clone(),
+            path: skill.path.clone(),
+            status,
+            trash_id: None,
+            error: None,
+        }
+    }
+}
+
+/// 按删除方式处理选中的 skills；`dry_run` 时只生成计划
+pub fn delete_selected(skills: &[&Skill], delete_mode: &DeleteMode, dry_run: bool) -> Vec<DeleteOutcome> {
+    skills
+        .iter()
+        .map(|skill| {
+            if dry_run {
+                return DeleteOutcome::new(skill, DeleteStatus::Planned);
+            }
+            match delete_mode.delete(&skill.path, &skill.name, &skill.tool) {
+                Ok(Some(trash_entry)) => DeleteOutcome {
+                    trash_id: Some(trash_entry.id),
+                    ..DeleteOutcome::new(skill, DeleteStatus::Trashed)
+                },
+                Ok(None) => DeleteOutcome::new(skill, DeleteStatus::Deleted),
+                Err(e) => DeleteOutcome {
+                    error: Some(e.to_string()),
+                    ..DeleteOutcome::new(skill, DeleteStatus::Failed)
+                },
+            }
+        })
+        .collect()
+}
+
+/// 同一目录可能被多个扫描根发现（如软链接或重叠的自定义目录），只保留第一次出现
+pub fn dedupe_by_path<'a>(skills: impl IntoIterator<Item = &'a Skill>) -> Vec<&'a Skill> {
+    let mut seen_paths: Vec<&Path> = Vec::new();
+    let mut unique_skills = Vec::new();
+    for skill in skills {
+        if !seen_paths.contains(&skill.path.as_path()) {
+            seen_paths.push(&skill.path);
+            unique_skills.push(skill);
+        }
+    }
+    unique_skills
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::ArtifactKind;
+    use crate::scanner::RootKind;
+
+    fn skill_at(name: &str, tool: &str, path: &str) -> Skill {
+        Skill {
+            name: name.to_string(),
+            tool: tool.to_string(),
+            kind: ArtifactKind::Skill,
+            category: None,
+            path: PathBuf::from(path),
+            description: None,
+            manifest: None,
+            root: PathBuf::new(),
+            root_kind: RootKind::Custom,
+            link_target: None,
+            rule: None,
+            extension: None,
+            content_hash: None,
+        }
+    }
+
+    #[test]
+    fn selector_combines_name_tool_and_path_conditions() {
+        let selector = SkillSelector::new(
+            &["pdf-*".to_string(), "docx".to_string()],
+            &["claude code*".to_string()],
+            &[PathBuf::from("/home/me")],
+        )
+        .expect("patterns should compile");
+
+        assert!(selector.matches(&skill_at("pdf-tools", "Claude Code", "/home/me/.claude/skills/pdf-tools")));
+        assert!(selector.matches(&skill_at("docx", "Claude Code (Project)", "/home/me/repo/.claude/skills/docx")));
+        assert!(!selector.matches(&skill_at("pdf-tools", "Cursor", "/home/me/.cursor/skills/pdf-tools")));
+        assert!(!selector.matches(&skill_at("pdf-tools", "Claude Code", "/opt/skills/pdf-tools")));
+        assert!(!selector.matches(&skill_at("xlsx", "Claude Code", "/home/me/.claude/skills/xlsx")));
+
+        let plain_tool_selector = SkillSelector::new(&[], &["codex".to_string()], &[]).expect("patterns should compile");
+        assert!(plain_tool_selector.matches(&skill_at("pdf", "OpenAI Codex (Project)", "/repo/.agents/skills/pdf")));
+        assert!(!plain_tool_selector.matches(&skill_at("pdf", "OpenCode", "/home/me/.config/opencode/skills/pdf")));
+    }
+
+    #[test]
+    fn dry_run_plans_without_touching_disk() {
+        let skill = skill_at("missing", "Cursor", "/definitely/not/here/missing");
+        let outcomes = delete_selected(&[&skill], &DeleteMode::Permanent, true);
+
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].status, DeleteStatus::Planned);
+
+        let outcomes = delete_selected(&[&skill], &DeleteMode::Permanent, false);
+        assert_eq!(outcomes[0].status, DeleteStatus::Failed);
+        assert!(outcomes[0].error.is_some());
+    }
+}
@@ -0,0 +1,215 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use serde::Serialize;
+
+use crate::content::{self, FileDiff};
+use crate::skill::{self, Skill};
+
+/// 参与比较的一份 skill 副本
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillCopy {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    pub tool: String,
+    pub path: PathBuf,
+    pub content_hash: String,
+}
+
+impl SkillCopy {
+    fn new(skill: &Skill, content_hash: &str) -> Self {
+        Self {
+            name: skill.name.clone(),
+            category: skill.category.clone(),
+            tool: skill.tool.clone(),
+            path: skill.path.clone(),
+            content_hash: content_hash.to_string(),
+        }
+    }
+}
+
+/// 同名（含分类）且内容完全一致的多份副本
+#[derive(Debug, Clone, Serialize)]
+pub struct IdenticalGroup {
+    pub name: String,
+    pub content_hash: String,
+    pub copies: Vec<SkillCopy>,
+}
+
+/// 分叉副本相对基准副本的差异
+#[derive(Debug, Clone, Serialize)]
+pub struct DivergedCopy {
+    #[serde(flatten)]
+    pub copy: SkillCopy,
+    pub diff: FileDiff,
+}
+
+/// 同名（含分类）但内容已分叉的多份副本；以第一份为基准列出其余副本的文件差异
+#[derive(Debug, Clone, Serialize)]
+pub struct DivergedGroup {
+    pub name: String,
+    /// 不同内容版本的数量
+    pub variant_count: usize,
+    pub reference: SkillCopy,
+    pub copies: Vec<DivergedCopy>,
+}
+
+/// 名称（含分类）不同但内容完全一致的副本
+#[derive(Debug, Clone, Serialize)]
+pub struct RenamedGroup {
+    pub content_hash: String,
+    pub copies: Vec<SkillCopy>,
+}
+
+/// `dupes` 报告
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DupeReport {
+    pub identical: Vec<IdenticalGroup>,
+    pub diverged: Vec<DivergedGroup>,
+    pub renamed: Vec<RenamedGroup>,
+}
+
+impl DupeReport {
+    pub fn is_empty(&self) -> bool {
+        self.identical.is_empty() && self.diverged.is_empty() && self.renamed.is_empty()
+    }
+}
+
+/// 比较扫描到的 skills 的内容哈希，找出重复与分叉的副本
+///
+/// 按带分类的名称分组，不同分类下的同名 skill 视为不同的 skill；
+/// 链接与其指向的目录只算一份；无法读取内容（没有哈希）的 skill 不参与比较。
+pub fn find_dupes(skills: &[Skill]) -> DupeReport {
+    let hashed_skills: Vec<(&Skill, &str)> = skill::dedupe_by_real_path(skills)
+        .into_iter()
+        .filter_map(|skill| Some((skill, skill.content_hash.as_deref()?)))
+        .collect();
+    let mut report = DupeReport::default();
+
+    let mut copies_by_name: BTreeMap<String, Vec<(&Skill, &str)>> = BTreeMap::new();
+    for &(skill, content_hash) in &hashed_skills {
+        copies_by_name
+            .entry(skill.qualified_name())
+            .or_default()
+            .push((skill, content_hash));
+    }
+    for (name, copies) in copies_by_name {
+        if copies.len() < 2 {
+            continue;
+        }
+        let distinct_hashes: BTreeSet<&str> = copies.iter().map(|&(_, content_hash)| content_hash).collect();
+        if distinct_hashes.len() == 1 {
+            report.identical.push(IdenticalGroup {
+                name,
+                content_hash: copies[0].1.to_string(),
+                copies: copies
+                    .iter()
+                    .map(|&(skill, content_hash)| SkillCopy::new(skill, content_hash))
+                    .collect(),
+            });
+            continue;
+        }
+
+        let (reference_skill, reference_hash) = copies[0];
+        let reference_file_hashes = content::hash_files(&reference_skill.path).unwrap_or_default();
+        report.diverged.push(DivergedGroup {
+            name,
+            variant_count: distinct_hashes.len(),
+            reference: SkillCopy::new(reference_skill, reference_hash),
+            copies: copies[1..]
+                .iter()
+                .map(|&(skill, content_hash)| DivergedCopy {
+                    copy: SkillCopy::new(skill, content_hash),
+                    diff: if content_hash == reference_hash {
+                        FileDiff::default()
+                    } else {
+                        let file_hashes = content::hash_files(&skill.path).unwrap_or_default();
+                        FileDiff::between(&reference_file_hashes, &file_hashes)
+                    },
+                })
+                .collect(),
+        });
+    }
+
+    let mut copies_by_hash: BTreeMap<&str, Vec<&Skill>> = BTreeMap::new();
+    for &(skill, content_hash) in &hashed_skills {
+        copies_by_hash.entry(content_hash).or_default().push(skill);
+    }
+    for (content_hash, copies) in copies_by_hash {
+        let distinct_names: BTreeSet<String> = copies.iter().map(|skill| skill.qualified_name()).collect();
+        if distinct_names.len() > 1 {
+            report.renamed.push(RenamedGroup {
+                content_hash: content_hash.to_string(),
+                copies: copies
+                    .iter()
+                    .map(|skill| SkillCopy::new(skill, content_hash))
+                    .collect(),
+            });
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+    use crate::artifact::ArtifactKind;
+    use crate::scanner::{RootKind, ScanRoot};
+    use std::fs;
+    use std::path::Path;
+
+    fn write_skill(root_path: &Path, tool_name: &str, name: &str, body: &str) -> Skill {
+        let skill_path = root_path.join(name);
+        fs::create_dir_all(&skill_path).expect("failed to create skill directory");
+        fs::write(skill_path.join("SKILL.md"), body).expect("failed to write SKILL.md");
+        Skill::from_path(
+            skill_path,
+            &ScanRoot {
+                tool_name: tool_name.to_string(),
+                path: root_path.to_path_buf(),
+                kind: RootKind::Custom,
+                max_depth: None,
+                artifact_kind: ArtifactKind::Skill,
+            },
+        )
+        .expect("skill should load")
+    }
+
+    #[test]
+    fn report_separates_identical_diverged_and_renamed_copies() {
+        let temp_directory_path = create_temp_directory("dupes");
+        let claude_root_path = temp_directory_path.join("claude");
+        let cursor_root_path = temp_directory_path.join("cursor");
+        let codex_root_path = temp_directory_path.join("codex");
+        let skills = vec![
+            write_skill(&claude_root_path, "Claude Code", "pdf", "# pdf"),
+            write_skill(&cursor_root_path, "Cursor", "pdf", "# pdf"),
+            write_skill(&claude_root_path, "Claude Code", "docx", "# docx v1"),
+            write_skill(&cursor_root_path, "Cursor", "docx", "# docx v2"),
+            write_skill(&codex_root_path, "OpenAI Codex", "pdf-tools", "# pdf"),
+            write_skill(&claude_root_path, "Claude Code", "frontend/utils", "# frontend utils"),
+            write_skill(&cursor_root_path, "Cursor", "backend/utils", "# backend utils"),
+        ];
+
+        let report = find_dupes(&skills);
+
+        // 不同分类下的同名 utils 是不同的 skill，不算分叉
+        assert!(report.diverged.iter().all(|group| !group.name.ends_with("utils")));
+        // pdf 还有一份改名的 pdf-tools，但同名的两份仍是一致的
+        assert_eq!(report.identical.len(), 1);
+        assert_eq!(report.identical[0].name, "pdf");
+        assert_eq!(report.identical[0].copies.len(), 2);
+
+        assert_eq!(report.diverged.len(), 1);
+        assert_eq!(report.diverged[0].name, "docx");
+        assert_eq!(report.diverged[0].variant_count, 2);
+        assert_eq!(report.diverged[0].copies[0].diff.modified, vec!["SKILL.md".to_string()]);
+
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].copies.len(), 3);
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+use std::io;
+
+use crate::install::InstallError;
+use crate::registry::RegistryError;
+use crate::trash::TrashError;
+
+/// 库中各类操作的错误汇总，便于调用方统一用 `?` 传播
+#[derive(Debug)]
+pub enum Error {
+    /// 文件系统操作失败
+    Io(io::Error),
+    /// 工具注册表加载失败
+    Registry(RegistryError),
+    /// 回收站操作失败
+    Trash(TrashError),
+    /// 定位 skill 或目标工具失败
+    Install(InstallError),
+    /// glob 模式语法错误
+    Pattern(glob::PatternError),
+    /// 文件系统监视失败
+    Watch(notify::Error),
+    /// 扫描线程池创建失败
+    ThreadPool(rayon::ThreadPoolBuildError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(source) => write!(f, "{}", source),
+            Error::Registry(source) => write!(f, "工具注册表加载失败: {}", source),
+            Error::Trash(source) => write!(f, "{}", source),
+            Error::Install(source) => write!(f, "{}", source),
+            Error::Pattern(source) => write!(f, "无效的 glob 模式: {}", source),
+            Error::Watch(source) => write!(f, "文件系统监视失败: {}", source),
+            Error::ThreadPool(source) => write!(f, "扫描线程池创建失败: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(source) => Some(source),
+            Error::Registry(source) => Some(source),
+            Error::Trash(source) => Some(source),
+            Error::Install(source) => Some(source),
+            Error::Pattern(source) => Some(source),
+            Error::Watch(source) => Some(source),
+            Error::ThreadPool(source) => Some(source),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io(source)
+    }
+}
+
+impl From<RegistryError> for Error {
+    fn from(source: RegistryError) -> Self {
+        Error::Registry(source)
+    }
+}
+
+impl From<TrashError> for Error {
+    fn from(source: TrashError) -> Self {
+        Error::Trash(source)
+    }
+}
+
+impl From<InstallError> for Error {
+    fn from(source: InstallError) -> Self {
+        Error::Install(source)
+    }
+}
+
+impl From<glob::PatternError> for Error {
+    fn from(source: glob::PatternError) -> Self {
+        Error::Pattern(source)
+    }
+}
+
+impl From<notify::Error> for Error {
+    fn from(source: notify::Error) -> Self {
+        Error::Watch(source)
+    }
+}
+
+impl From<rayon::ThreadPoolBuildError> for Error {
+    fn from(source: rayon::ThreadPoolBuildError) -> Self {
+        Error::ThreadPool(source)
+    }
+}
+
+/// 以 [`Error`] 为错误类型的 `Result`
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+use serde::Serialize;
+
+/// 问题严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// 必须修复
+    Error,
+    /// 建议修复
+    Warning,
+    /// 仅供参考
+    Note,
+}
+
+impl Severity {
+    /// 用于文本输出的标签
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+/// 一条检查规则的静态描述
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    /// 稳定的规则 ID
+    pub id: &'static str,
+    /// 默认严重程度
+    pub severity: Severity,
+    /// 一句话说明
+    pub summary: &'static str,
+}
+
+/// 对某个 skill 中某个文件位置报告的问题
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    /// 触发的规则 ID
+    pub rule_id: &'static str,
+    /// 严重程度
+    pub severity: Severity,
+    /// 问题描述
+    pub message: String,
+    /// 所属 skill 名称
+    pub skill: String,
+    /// 所属工具名称
+    pub tool: String,
+    /// 问题所在文件
+    pub path: PathBuf,
+    /// 行号（从 1 开始）
+    pub line: Option<usize>,
+    /// 列号（从 1 开始）
+    pub column: Option<usize>,
+}
+
+impl Finding {
+    /// 按规则默认严重程度创建
+    pub fn new(rule: &Rule, message: impl Into<String>, path: PathBuf) -> Self {
+        Self {
+            rule_id: rule.id,
+            severity: rule.severity,
+            message: message.into(),
+            skill: String::new(),
+            tool: String::new(),
+            path,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// 设置行列位置
+    pub fn at(mut self, line: usize, column: usize) -> Self {
+        self.line = Some(line);
+        self.column = Some(column);
+        self
+    }
+
+    /// 是否为错误级别
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
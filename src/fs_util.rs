@@ -0,0 +1,96 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 递归复制目录；符号链接在 Unix 上按链接本身复制，其他平台复制链接目标
+pub fn copy_dir_all(source_path: &Path, destination_path: &Path) -> io::Result<()> {
+    fs::create_dir_all(destination_path)?;
+    for entry in fs::read_dir(source_path)? {
+        let entry = entry?;
+        let entry_source_path = entry.path();
+        let entry_destination_path = destination_path.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_symlink() {
+            copy_symlink(&entry_source_path, &entry_destination_path)?;
+        } else if file_type.is_dir() {
+            copy_dir_all(&entry_source_path, &entry_destination_path)?;
+        } else {
+            fs::copy(&entry_source_path, &entry_destination_path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn copy_symlink(source_path: &Path, destination_path: &Path) -> io::Result<()> {
+    let link_target_path = fs::read_link(source_path)?;
+    std::os::unix::fs::symlink(link_target_path, destination_path)
+}
+
+#[cfg(not(unix))]
+fn copy_symlink(source_path: &Path, destination_path: &Path) -> io::Result<()> {
+    if source_path.is_dir() {
+        copy_dir_all(source_path, destination_path)
+    } else {
+        fs::copy(source_path, destination_path).map(|_| ())
+    }
+}
+
+/// 移动文件或目录：优先 rename，跨文件系统时回退为复制后删除
+///
+/// 失败时若目标不存在，源内容一定保持不动；若目标已存在，则是删除源时出错，目标为完整副本。
+pub fn move_path(source_path: &Path, destination_path: &Path) -> io::Result<()> {
+    if let Some(parent_directory_path) = destination_path.parent() {
+        fs::create_dir_all(parent_directory_path)?;
+    }
+    if fs::rename(source_path, destination_path).is_ok() {
+        return Ok(());
+    }
+
+    let source_metadata = fs::symlink_metadata(source_path)?;
+    let copied = if source_metadata.file_type().is_symlink() {
+        copy_symlink(source_path, destination_path)
+    } else if source_metadata.is_dir() {
+        copy_dir_all(source_path, destination_path)
+    } else {
+        fs::copy(source_path, destination_path).map(|_| ())
+    };
+    if let Err(error) = copied {
+        // 复制不完整时清理目标，源内容保持不动；目标存在即表示源已完整复制
+        let _ = remove_path(destination_path);
+        return Err(error);
+    }
+    remove_path(source_path)
+}
+
+/// 删除文件、目录或符号链接（不跟随链接删除目标）
+pub fn remove_path(path: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        remove_file_or_link(path)
+    }
+}
+
+#[cfg(windows)]
+fn remove_file_or_link(path: &Path) -> io::Result<()> {
+    // Windows 上指向目录的符号链接/junction 需要用 remove_dir 删除
+    fs::remove_file(path).or_else(|_| fs::remove_dir(path))
+}
+
+#[cfg(not(windows))]
+fn remove_file_or_link(path: &Path) -> io::Result<()> {
+    fs::remove_file(path)
+}
+
+/// 相对路径按当前目录补全为绝对路径
+pub fn absolutize(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        return path.to_path_buf();
+    }
+    std::env::current_dir()
+        .map(|current_directory_path| current_directory_path.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,483 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::artifact::ArtifactKind;
+use crate::fs_util;
+use crate::link;
+use crate::registry::{self, RootBase, ToolRegistry};
+use crate::scanner::{self, RootKind, ScanRoot};
+use crate::skill::{self, Skill};
+use crate::trash::DeleteMode;
+
+/// 安装范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InstallScope {
+    /// 用户级目录（`~` 或平台配置目录下）
+    User,
+    /// 当前工作区（git 根）下的项目级目录
+    Project,
+}
+
+/// 目标位置已存在同名 skill 时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// 跳过，保留已有内容
+    Skip,
+    /// 将已有内容移入回收站（或按 `--permanent` 删除）后覆盖
+    Overwrite,
+    /// 以 `<name>-2`、`<name>-3` 等新名称安装
+    Rename,
+}
+
+/// 安装方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallMethod {
+    /// 复制整个 skill 目录
+    Copy,
+    /// 创建指向规范副本的链接（Windows 上为符号链接或 junction）
+    Link,
+}
+
+/// 可安装到的一个工具 skills 目录
+#[derive(Debug, Clone)]
+pub struct InstallTarget {
+    /// 注册表中的工具名称
+    pub tool_name: String,
+    /// 该工具的 skills 目录（可能尚不存在）
+    pub skills_directory_path: PathBuf,
+}
+
+/// 定位源 skill 或目标工具失败的原因
+#[derive(Debug)]
+pub enum InstallError {
+    /// 找不到指定名称的 skill
+    UnknownSkill { query: String },
+    /// 多个 skill 同名，需要 `--from` 指定来源
+    AmbiguousSkill { query: String, candidates: Vec<String> },
+    /// 找不到指定的工具
+    UnknownTool { query: String, scope: InstallScope },
+    /// 工具名称匹配到多个条目
+    AmbiguousTool { query: String, candidates: Vec<String> },
+}
+
+impl fmt::Display for InstallError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstallError::UnknownSkill { query } => write!(f, "找不到 skill `{}`", query),
+            InstallError::AmbiguousSkill { query, candidates } => write!(
+                f,
+                "存在多个名为 `{}` 的 skill，请用 --from 指定来源工具:\n  {}",
+                query,
+                candidates.join("\n  ")
+            ),
+            InstallError::UnknownTool { query, scope } => {
+                let scope_label = match scope {
+                    InstallScope::User => "用户级",
+                    InstallScope::Project => "项目级",
+                };
+                write!(f, "注册表中没有名为 `{}` 的{}工具目录", query, scope_label)
+            }
+            InstallError::AmbiguousTool { query, candidates } => write!(
+                f,
+                "`{}` 匹配到多个工具，请使用完整名称: {}",
+                query,
+                candidates.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InstallError {}
+
+/// 指定范围内所有可安装的工具目录（只含 skill 目录，不含历史兼容目录与系统目录）
+pub fn install_targets(registry: &ToolRegistry, scope: InstallScope) -> Vec<InstallTarget> {
+    let base_directories: Vec<(RootBase, Option<PathBuf>)> = match scope {
+        InstallScope::User => vec![
+            (RootBase::Home, dirs::home_dir()),
+            (RootBase::Config, dirs::config_dir()),
+        ],
+        InstallScope::Project => vec![(RootBase::Workspace, scanner::workspace_root())],
+    };
+
+    let mut seen_directory_paths = HashSet::new();
+    let mut targets = Vec::new();
+    for (root_base, base_directory_path) in base_directories {
+        let Some(base_directory_path) = base_directory_path else {
+            continue;
+        };
+        for tool_entry in registry.enabled_entries(root_base) {
+            if tool_entry.kind != ArtifactKind::Skill || registry::is_legacy_tool(&tool_entry.name) {
+                continue;
+            }
+            if let Ok(skills_directory_path) = tool_entry.resolve(&base_directory_path) {
+                if seen_directory_paths.insert(skills_directory_path.clone()) {
+                    targets.push(InstallTarget {
+                        tool_name: tool_entry.name.clone(),
+                        skills_directory_path,
+                    });
+                }
+            }
+        }
+    }
+    targets
+}
+
+/// 按名称查找安装目标，支持完整名称、工具族名称（去掉括号后缀）或其唯一子串，均不区分大小写
+pub fn resolve_install_target(
+    registry: &ToolRegistry,
+    query: &str,
+    scope: InstallScope,
+) -> Result<InstallTarget, InstallError> {
+    let targets = install_targets(registry, scope);
+    let tool_names: Vec<&str> = targets.iter().map(|target| target.tool_name.as_str()).collect();
+    let target_index = match_tool_query(&tool_names, query).map_err(|candidates| {
+        if candidates.is_empty() {
+            InstallError::UnknownTool {
+                query: query.to_string(),
+                scope,
+            }
+        } else {
+            InstallError::AmbiguousTool {
+                query: query.to_string(),
+                candidates,
+            }
+        }
+    })?;
+    Ok(targets[target_index].clone())
+}
+
+/// 在工具名称中查找查询串；失败时返回匹配到的候选（为空表示没有匹配）
+pub fn match_tool_query(tool_names: &[&str], query: &str) -> Result<usize, Vec<String>> {
+    let query = query.trim().to_lowercase();
+    let find_unique = |is_match: &dyn Fn(&str) -> bool| -> Result<Option<usize>, Vec<String>> {
+        let matched_indices: Vec<usize> = (0..tool_names.len())
+            .filter(|&index| is_match(tool_names[index]))
+            .collect();
+        match matched_indices.as_slice() {
+            [] => Ok(None),
+            [index] => Ok(Some(*index)),
+            _ => Err(matched_indices
+                .iter()
+                .map(|&index| tool_names[index].to_string())
+                .collect()),
+        }
+    };
+
+    let strategies: [&dyn Fn(&str) -> bool; 3] = [
+        &|tool_name| tool_name.to_lowercase() == query,
+        &|tool_name| registry::tool_family(tool_name).to_lowercase() == query,
+        &|tool_name| registry::tool_family(tool_name).to_lowercase().contains(&query),
+    ];
+    for is_match in strategies {
+        if let Some(index) = find_unique(is_match)? {
+            return Ok(index);
+        }
+    }
+    Err(Vec::new())
+}
+
+/// 定位要安装的源 skill：可以是 skill 目录路径，也可以是扫描结果中的名称或 `分类/名称`
+pub fn find_source_skill(
+    skills: &[Skill],
+    query: &str,
+    from_tool: Option<&str>,
+) -> Result<Skill, InstallError> {
+    let query_path = Path::new(query);
+    if query_path.join("SKILL.md").is_file() {
+        let source_path = fs_util::absolutize(query_path);
+        let scan_root = ScanRoot {
+            tool_name: "Custom".to_string(),
+            path: source_path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            kind: RootKind::Custom,
+            max_depth: None,
+            artifact_kind: ArtifactKind::Skill,
+        };
+        return Skill::from_path(source_path, &scan_root).ok_or_else(|| InstallError::UnknownSkill {
+            query: query.to_string(),
+        });
+    }
+
+    // 链接与其规范副本视为同一个 skill，优先使用规范副本
+    let named_skills = skill::dedupe_by_real_path(
+        skills
+            .iter()
+            .filter(|skill| skill.name == query || skill.qualified_name() == query),
+    );
+    let candidate_skills: Vec<&Skill> = match from_tool {
+        Some(from_tool) => {
+            let tool_names: Vec<&str> = named_skills.iter().map(|skill| skill.tool.as_str()).collect();
+            match match_tool_query(&tool_names, from_tool) {
+                Ok(index) => vec![named_skills[index]],
+                Err(matched_tool_names) => named_skills
+                    .into_iter()
+                    .filter(|skill| matched_tool_names.contains(&skill.tool))
+                    .collect(),
+            }
+        }
+        None => named_skills,
+    };
+
+    match candidate_skills.as_slice() {
+        [] => Err(InstallError::UnknownSkill {
+            query: query.to_string(),
+        }),
+        [skill] => Ok((*skill).clone()),
+        _ => Err(InstallError::AmbiguousSkill {
+            query: query.to_string(),
+            candidates: candidate_skills
+                .iter()
+                .map(|skill| format!("{}: {}", skill.tool, skill.path.display()))
+                .collect(),
+        }),
+    }
+}
+
+/// 单个目标的安装结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InstallStatus {
+    /// 新安装（复制）
+    Installed,
+    /// 已创建链接
+    Linked,
+    /// 覆盖了已有内容
+    Replaced,
+    /// 以新名称安装
+    Renamed,
+    /// 已跳过
+    Skipped,
+    /// 安装失败
+    Failed,
+}
+
+/// 安装结果记录，用于文本与 JSON 输出
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallOutcome {
+    pub name: String,
+    pub tool: String,
+    pub destination: PathBuf,
+    pub status: InstallStatus,
+    /// 链接安装时指向的规范副本
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_target: Option<PathBuf>,
+    /// 被覆盖内容在回收站中的 ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
+    /// 跳过或失败的原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 将 skill 复制或链接到目标工具的 skills 目录，必要时创建目录
+///
+/// 链接安装时始终指向源 skill 解析后的真实目录，源本身是链接也不会形成链式链接。
+pub fn install_skill(
+    source_skill: &Skill,
+    target: &InstallTarget,
+    install_method: InstallMethod,
+    conflict_policy: ConflictPolicy,
+    delete_mode: &DeleteMode,
+) -> InstallOutcome {
+    let mut destination_path = target.skills_directory_path.join(&source_skill.name);
+    let mut outcome = InstallOutcome {
+        name: source_skill.name.clone(),
+        tool: target.tool_name.clone(),
+        destination: destination_path.clone(),
+        status: InstallStatus::Installed,
+        link_target: None,
+        trash_id: None,
+        message: None,
+    };
+
+    if is_same_directory(&source_skill.path, &destination_path) {
+        outcome.status = InstallStatus::Skipped;
+        outcome.message = Some(if link::read_link_target(&destination_path).is_some() {
+            "目标位置已链接到同一 skill".to_string()
+        } else {
+            "源目录与目标目录相同".to_string()
+        });
+        return outcome;
+    }
+
+    if fs::symlink_metadata(&destination_path).is_ok() {
+        match conflict_policy {
+            ConflictPolicy::Skip => {
+                outcome.status = InstallStatus::Skipped;
+                outcome.message = Some("目标位置已存在同名 skill".to_string());
+                return outcome;
+            }
+            ConflictPolicy::Overwrite => {
+                match delete_mode.delete(&destination_path, &source_skill.name, &target.tool_name) {
+                    Ok(trash_entry) => {
+                        outcome.status = InstallStatus::Replaced;
+                        outcome.trash_id = trash_entry.map(|trash_entry| trash_entry.id);
+                    }
+                    Err(e) => {
+                        outcome.status = InstallStatus::Failed;
+                        outcome.message = Some(format!("无法移除已有内容: {}", e));
+                        return outcome;
+                    }
+                }
+            }
+            ConflictPolicy::Rename => {
+                destination_path = available_destination_path(&target.skills_directory_path, &source_skill.name);
+                outcome.destination = destination_path.clone();
+                outcome.status = InstallStatus::Renamed;
+            }
+        }
+    }
+
+    let install_result = match install_method {
+        InstallMethod::Copy => fs_util::copy_dir_all(&source_skill.path, &destination_path).inspect_err(|_| {
+            // 清理复制了一半的目录
+            let _ = fs::remove_dir_all(&destination_path);
+        }),
+        InstallMethod::Link => fs::canonicalize(&source_skill.path).and_then(|canonical_path| {
+            link::create_directory_link(&canonical_path, &destination_path)?;
+            outcome.link_target = Some(canonical_path);
+            if outcome.status == InstallStatus::Installed {
+                outcome.status = InstallStatus::Linked;
+            }
+            Ok(())
+        }),
+    };
+    if let Err(e) = install_result {
+        outcome.status = InstallStatus::Failed;
+        outcome.message = Some(e.to_string());
+    }
+    outcome
+}
+
+/// 为重命名安装找到第一个未被占用的 `<name>-N`
+fn available_destination_path(skills_directory_path: &Path, skill_name: &str) -> PathBuf {
+    (2..)
+        .map(|sequence| skills_directory_path.join(format!("{}-{}", skill_name, sequence)))
+        .find(|candidate_path| fs::symlink_metadata(candidate_path).is_err())
+        .expect("sequence is unbounded")
+}
+
+fn is_same_directory(source_path: &Path, destination_path: &Path) -> bool {
+    match (fs::canonicalize(source_path), fs::canonicalize(destination_path)) {
+        (Ok(canonical_source_path), Ok(canonical_destination_path)) => {
+            canonical_source_path == canonical_destination_path
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+
+    #[test]
+    fn match_tool_query_prefers_exact_then_family_then_substring() {
+        let tool_names = ["Claude Code", "OpenAI Codex", "Gemini CLI", "Cursor", "Cursor (Project)"];
+
+        assert_eq!(match_tool_query(&tool_names, "cursor (project)"), Ok(4));
+        assert_eq!(match_tool_query(&tool_names, "claude"), Ok(0));
+        assert_eq!(match_tool_query(&tool_names, "codex"), Ok(1));
+        assert_eq!(match_tool_query(&tool_names, "GEMINI"), Ok(2));
+        assert_eq!(match_tool_query(&tool_names, "cursor"), Ok(3));
+        assert_eq!(
+            match_tool_query(&tool_names, "code"),
+            Err(vec!["Claude Code".to_string(), "OpenAI Codex".to_string()])
+        );
+        assert_eq!(match_tool_query(&tool_names, "windsurf"), Err(Vec::new()));
+    }
+
+    #[test]
+    fn install_targets_only_include_skill_directories() {
+        let registry = ToolRegistry::builtin();
+        let codex_target = resolve_install_target(&registry, "codex", InstallScope::User)
+            .expect("codex should resolve to a single skills directory");
+        assert_eq!(codex_target.tool_name, "OpenAI Codex");
+        assert!(install_targets(&registry, InstallScope::User)
+            .iter()
+            .all(|target| target.skills_directory_path.ends_with("skills")));
+        for command_tool_name in ["Claude Code Commands", "Claude Code Agents"] {
+            assert!(matches!(
+                resolve_install_target(&registry, command_tool_name, InstallScope::User),
+                Err(InstallError::UnknownTool { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn install_skill_applies_conflict_policy() {
+        let temp_directory_path = create_temp_directory("install-conflict");
+        let source_directory_path = temp_directory_path.join("source").join("pdf");
+        fs::create_dir_all(source_directory_path.join("scripts")).expect("failed to create source skill");
+        fs::write(source_directory_path.join("SKILL.md"), "new").expect("failed to write SKILL.md");
+        fs::write(source_directory_path.join("scripts").join("run.sh"), "echo").expect("failed to write script");
+        let source_skill = find_source_skill(&[], source_directory_path.to_str().unwrap(), None)
+            .expect("source skill should be found by path");
+
+        let target = InstallTarget {
+            tool_name: "Cursor".to_string(),
+            skills_directory_path: temp_directory_path.join("cursor").join("skills"),
+        };
+        let installed_path = target.skills_directory_path.join("pdf");
+
+        let first_outcome = install_skill(
+            &source_skill,
+            &target,
+            InstallMethod::Copy,
+            ConflictPolicy::Skip,
+            &DeleteMode::Permanent,
+        );
+        assert_eq!(first_outcome.status, InstallStatus::Installed);
+        assert!(installed_path.join("scripts").join("run.sh").is_file());
+
+        fs::write(installed_path.join("SKILL.md"), "old").expect("failed to modify installed skill");
+        let skipped_outcome = install_skill(
+            &source_skill,
+            &target,
+            InstallMethod::Copy,
+            ConflictPolicy::Skip,
+            &DeleteMode::Permanent,
+        );
+        assert_eq!(skipped_outcome.status, InstallStatus::Skipped);
+        assert_eq!(fs::read_to_string(installed_path.join("SKILL.md")).unwrap(), "old");
+
+        let renamed_outcome = install_skill(
+            &source_skill,
+            &target,
+            InstallMethod::Copy,
+            ConflictPolicy::Rename,
+            &DeleteMode::Permanent,
+        );
+        assert_eq!(renamed_outcome.status, InstallStatus::Renamed);
+        assert_eq!(renamed_outcome.destination, target.skills_directory_path.join("pdf-2"));
+
+        let replaced_outcome = install_skill(
+            &source_skill,
+            &target,
+            InstallMethod::Copy,
+            ConflictPolicy::Overwrite,
+            &DeleteMode::Permanent,
+        );
+        assert_eq!(replaced_outcome.status, InstallStatus::Replaced);
+        assert_eq!(fs::read_to_string(installed_path.join("SKILL.md")).unwrap(), "new");
+
+        let self_target = InstallTarget {
+            tool_name: "Custom".to_string(),
+            skills_directory_path: temp_directory_path.join("source"),
+        };
+        let self_outcome = install_skill(
+            &source_skill,
+            &self_target,
+            InstallMethod::Copy,
+            ConflictPolicy::Overwrite,
+            &DeleteMode::Permanent,
+        );
+        assert_eq!(self_outcome.status, InstallStatus::Skipped);
+        assert!(source_directory_path.join("SKILL.md").is_file());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
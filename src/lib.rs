@@ -0,0 +1,70 @@
+//! 扫描并管理本地 AI 工具（Claude Code、Cursor、Codex 等）的 skills。
+//!
+//! 命令行工具 `skills-scanner` 基于本库实现；其他 Rust 程序也可以直接嵌入扫描器：
+//!
+//! ```
+//! use skills_scanner::{ScanOptions, Scanner, ToolRegistry};
+//!
+//! let mut scanner = Scanner::from_registry(&ToolRegistry::builtin());
+//! scanner.set_options(ScanOptions {
+//!     exclude_tools: vec!["Cursor".to_string()],
+//!     ..ScanOptions::default()
+//! })?;
+//! for skill in scanner.scan() {
+//!     println!("{} > {}  {}", skill.tool, skill.name, skill.path.display());
+//! }
+//! # Ok::<(), skills_scanner::Error>(())
+//! ```
+//!
+//! 只扫描指定目录时使用 [`Scanner::empty`]：
+//!
+//! ```
+//! use std::fs;
+//! use skills_scanner::Scanner;
+//!
+//! let skills_directory_path = std::env::temp_dir().join(format!("skills-doc-{}", std::process::id()));
+//! fs::create_dir_all(skills_directory_path.join("pdf"))?;
+//! fs::write(skills_directory_path.join("pdf").join("SKILL.md"), "---\nname: pdf\ndescription: 处理 PDF\n---\n")?;
+//!
+//! let mut scanner = Scanner::empty();
+//! scanner.add_custom_path(skills_directory_path.clone());
+//! let skills = scanner.scan();
+//! assert_eq!(skills.len(), 1);
+//! assert_eq!(skills[0].display_description(), "处理 PDF");
+//!
+//! fs::remove_dir_all(&skills_directory_path)?;
+//! # Ok::<(), skills_scanner::Error>(())
+//! ```
+
+pub mod artifact;
+pub mod audit;
+pub mod cache;
+pub mod content;
+pub mod delete;
+pub mod dupes;
+pub mod error;
+pub mod finding;
+pub mod fs_util;
+pub mod install;
+pub mod link;
+pub mod lint;
+pub mod manifest;
+pub mod mcp;
+pub mod mcp_server;
+pub mod registry;
+pub mod resolve;
+pub mod sarif;
+pub mod scanner;
+pub mod skill;
+pub mod sync;
+pub mod trash;
+pub mod watch;
+pub mod web;
+
+#[cfg(test)]
+mod test_support;
+
+pub use error::{Error, Result};
+pub use registry::{ToolEntry, ToolRegistry};
+pub use scanner::{RootKind, ScanOptions, ScanRoot, Scanner};
+pub use skill::Skill;
@@ -0,0 +1,389 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::fs_util;
+use crate::scanner::Scanner;
+use crate::skill::Skill;
+
+/// 创建指向目录的链接：Unix 上为符号链接，Windows 上优先符号链接，无权限时回退为 junction
+pub fn create_directory_link(target_path: &Path, link_path: &Path) -> io::Result<()> {
+    if let Some(parent_directory_path) = link_path.parent() {
+        fs::create_dir_all(parent_directory_path)?;
+    }
+    create_platform_directory_link(target_path, link_path)
+}
+
+#[cfg(unix)]
+fn create_platform_directory_link(target_path: &Path, link_path: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target_path, link_path)
+}
+
+#[cfg(windows)]
+fn create_platform_directory_link(target_path: &Path, link_path: &Path) -> io::Result<()> {
+    if std::os::windows::fs::symlink_dir(target_path, link_path).is_ok() {
+        return Ok(());
+    }
+    // 未开启开发者模式时创建符号链接需要管理员权限，junction 则不需要
+    let status = std::process::Command::new("cmd")
+        .arg("/C")
+        .arg("mklink")
+        .arg("/J")
+        .arg(link_path)
+        .arg(target_path)
+        .stdout(std::process::Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("mklink /J 失败: {}", status)))
+    }
+}
+
+/// 若路径本身是符号链接（或 junction），返回其指向的绝对路径
+pub fn read_link_target(path: &Path) -> Option<PathBuf> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    if !metadata.file_type().is_symlink() {
+        return None;
+    }
+    let link_target_path = fs::read_link(path).ok()?;
+    if link_target_path.is_absolute() {
+        return Some(link_target_path);
+    }
+    let parent_directory_path = path.parent().unwrap_or_else(|| Path::new(""));
+    Some(parent_directory_path.join(link_target_path))
+}
+
+/// 扫描根目录下目标已不存在的链接
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenLink {
+    pub name: String,
+    pub tool: String,
+    pub path: PathBuf,
+    pub link_target: PathBuf,
+}
+
+/// 在扫描根目录的发现深度内查找失效链接
+///
+/// 与扫描一致：跳过忽略的目录，不进入 skill 目录内部，其余目录视为分类目录继续查找。
+pub fn find_broken_links(scanner: &Scanner) -> Vec<BrokenLink> {
+    let mut broken_links = Vec::new();
+    for scan_root in scanner.roots() {
+        let extractor = scan_root.artifact_kind.extractor();
+        let mut directory_walker = WalkDir::new(&scan_root.path)
+            .min_depth(1)
+            .max_depth(scanner.discovery_depth(scan_root))
+            .sort_by_file_name()
+            .into_iter();
+        while let Some(entry) = directory_walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let entry_path = entry.path();
+            if entry.file_type().is_dir() {
+                if scanner.options().is_directory_ignored(entry.file_name()) || extractor.is_artifact(entry_path) {
+                    directory_walker.skip_current_dir();
+                }
+                continue;
+            }
+            let Some(link_target_path) = read_link_target(entry_path) else {
+                continue;
+            };
+            if !entry_path.exists() {
+                broken_links.push(BrokenLink {
+                    name: entry.file_name().to_string_lossy().into_owned(),
+                    tool: scan_root.tool_name.clone(),
+                    path: entry_path.to_path_buf(),
+                    link_target: link_target_path,
+                });
+            }
+        }
+    }
+    broken_links
+}
+
+/// 修复结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepairStatus {
+    /// dry-run：将重新指向新的规范副本
+    Planned,
+    /// 已重新指向新的规范副本
+    Relinked,
+    /// 已删除无法修复的链接（`--prune`）
+    Pruned,
+    /// 找不到唯一的规范副本，保持不变
+    Unresolved,
+    /// 修复失败
+    Failed,
+}
+
+/// 单个失效链接的修复结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RepairOutcome {
+    #[serde(flatten)]
+    pub broken_link: BrokenLink,
+    pub status: RepairStatus,
+    /// 新的链接目标
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_target: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 修复失效链接：若扫描结果中恰好有一个同名的真实目录，则将链接重新指向它
+pub fn repair_broken_links(
+    broken_links: Vec<BrokenLink>,
+    skills: &[Skill],
+    dry_run: bool,
+    prune: bool,
+) -> Vec<RepairOutcome> {
+    broken_links
+        .into_iter()
+        .map(|broken_link| {
+            let mut candidate_paths: Vec<PathBuf> = skills
+                .iter()
+                .filter(|skill| skill.name == broken_link.name && skill.link_target.is_none())
+                .filter_map(|skill| fs::canonicalize(&skill.path).ok())
+                .collect();
+            candidate_paths.sort();
+            candidate_paths.dedup();
+
+            let mut outcome = RepairOutcome {
+                broken_link,
+                status: RepairStatus::Unresolved,
+                new_target: None,
+                message: None,
+            };
+            let link_path = outcome.broken_link.path.clone();
+            match candidate_paths.as_slice() {
+                [new_target_path] => {
+                    outcome.new_target = Some(new_target_path.clone());
+                    outcome.status = if dry_run {
+                        RepairStatus::Planned
+                    } else {
+                        match remove_link(&link_path)
+                            .and_then(|_| create_directory_link(new_target_path, &link_path))
+                        {
+                            Ok(()) => RepairStatus::Relinked,
+                            Err(e) => {
+                                outcome.message = Some(e.to_string());
+                                RepairStatus::Failed
+                            }
+                        }
+                    };
+                }
+                _ if prune && !dry_run => {
+                    outcome.status = match remove_link(&link_path) {
+                        Ok(()) => RepairStatus::Pruned,
+                        Err(e) => {
+                            outcome.message = Some(e.to_string());
+                            RepairStatus::Failed
+                        }
+                    };
+                }
+                [] => outcome.message = Some("找不到同名的真实 skill 目录".to_string()),
+                _ => {
+                    outcome.message = Some(format!(
+                        "存在 {} 个同名的真实 skill 目录，无法确定规范副本",
+                        candidate_paths.len()
+                    ))
+                }
+            }
+            outcome
+        })
+        .collect()
+}
+
+/// 断开链接的结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnlinkStatus {
+    /// dry-run：将删除链接
+    Planned,
+    /// 已删除链接
+    Removed,
+    /// 已替换为链接目标的独立副本（`--copy`）
+    Materialized,
+    /// 处理失败
+    Failed,
+}
+
+/// 单个链接的断开结果
+#[derive(Debug, Clone, Serialize)]
+pub struct UnlinkOutcome {
+    pub name: String,
+    pub tool: String,
+    /// 链接所在路径
+    pub path: PathBuf,
+    /// 链接原来指向的路径
+    pub link_target: PathBuf,
+    pub status: UnlinkStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 断开 skills 的链接：`copy` 为 true 时替换为链接目标的独立副本，否则只删除链接
+pub fn unlink_skills(linked_skills: &[&Skill], copy: bool, dry_run: bool) -> Vec<UnlinkOutcome> {
+    linked_skills
+        .iter()
+        .map(|linked_skill| {
+            let mut outcome = UnlinkOutcome {
+                name: linked_skill.name.clone(),
+                tool: linked_skill.tool.clone(),
+                path: linked_skill.path.clone(),
+                link_target: linked_skill.link_target.clone().unwrap_or_else(|| linked_skill.path.clone()),
+                status: UnlinkStatus::Planned,
+                message: None,
+            };
+            if dry_run {
+                return outcome;
+            }
+
+            let unlink_result = if copy {
+                materialize_link(&linked_skill.path)
+            } else {
+                remove_link(&linked_skill.path)
+            };
+            outcome.status = match unlink_result {
+                Ok(()) if copy => UnlinkStatus::Materialized,
+                Ok(()) => UnlinkStatus::Removed,
+                Err(e) => {
+                    outcome.message = Some(e.to_string());
+                    UnlinkStatus::Failed
+                }
+            };
+            outcome
+        })
+        .collect()
+}
+
+/// 删除链接本身（不影响链接目标）；路径不是链接时拒绝
+pub fn remove_link(link_path: &Path) -> io::Result<()> {
+    if read_link_target(link_path).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} 不是链接", link_path.display()),
+        ));
+    }
+    fs_util::remove_path(link_path)
+}
+
+/// 将链接替换为其目标的独立副本：先复制到临时目录，成功后再替换链接
+pub fn materialize_link(link_path: &Path) -> io::Result<()> {
+    let link_target_path = read_link_target(link_path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} 不是链接", link_path.display()),
+        )
+    })?;
+    let mut temporary_file_name = link_path.file_name().unwrap_or_default().to_os_string();
+    temporary_file_name.push(".unlink-tmp");
+    let temporary_path = link_path.with_file_name(temporary_file_name);
+
+    if let Err(e) = fs_util::copy_dir_all(&link_target_path, &temporary_path) {
+        let _ = fs::remove_dir_all(&temporary_path);
+        return Err(e);
+    }
+    remove_link(link_path)?;
+    fs::rename(&temporary_path, link_path)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+    use crate::artifact::ArtifactKind;
+    use crate::scanner::{RootKind, ScanOptions, ScanRoot};
+
+    #[test]
+    fn scanner_marks_links_and_repair_relinks_broken_ones() {
+        let temp_directory_path = create_temp_directory("link-repair");
+        let canonical_root_path = temp_directory_path.join("canonical");
+        let old_skill_path = canonical_root_path.join("old").join("pdf");
+        fs::create_dir_all(&old_skill_path).expect("failed to create canonical skill");
+        fs::write(old_skill_path.join("SKILL.md"), "# pdf").expect("failed to write SKILL.md");
+
+        // 链接位于分类目录下，发现深度内的失效链接同样要找到
+        let tool_root_path = temp_directory_path.join("cursor-skills");
+        let link_path = tool_root_path.join("documents").join("pdf");
+        create_directory_link(&old_skill_path, &link_path).expect("failed to create link");
+
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(tool_root_path.clone());
+        scanner
+            .set_options(ScanOptions {
+                max_depth: Some(2),
+                ..ScanOptions::default()
+            })
+            .expect("failed to set scan options");
+        let skills = scanner.scan();
+        assert_eq!(skills.len(), 1);
+        assert_eq!(skills[0].link_target.as_deref(), Some(old_skill_path.as_path()));
+
+        // 规范副本被移动后，链接失效；修复时重新指向扫描到的同名真实目录
+        let new_skill_path = canonical_root_path.join("new").join("pdf");
+        fs::create_dir_all(new_skill_path.parent().unwrap()).expect("failed to create new parent");
+        fs::rename(&old_skill_path, &new_skill_path).expect("failed to move canonical skill");
+
+        let broken_links = find_broken_links(&scanner);
+        assert_eq!(broken_links.len(), 1);
+        assert_eq!(broken_links[0].name, "pdf");
+
+        let canonical_skill = Skill::from_path(
+            new_skill_path.clone(),
+            &ScanRoot {
+                tool_name: "Claude Code".to_string(),
+                path: new_skill_path.parent().unwrap().to_path_buf(),
+                kind: RootKind::UserHome,
+                max_depth: None,
+                artifact_kind: ArtifactKind::Skill,
+            },
+        )
+        .expect("canonical skill should load");
+        let outcomes = repair_broken_links(broken_links, &[canonical_skill], false, false);
+        assert_eq!(outcomes[0].status, RepairStatus::Relinked);
+        assert!(link_path.join("SKILL.md").is_file());
+        assert!(find_broken_links(&scanner).is_empty());
+
+        assert!(remove_link(&new_skill_path).is_err());
+        remove_link(&link_path).expect("failed to remove link");
+        assert!(new_skill_path.join("SKILL.md").is_file());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+
+    #[test]
+    fn unlink_skills_plans_on_dry_run_and_materializes_with_copy() {
+        let temp_directory_path = create_temp_directory("link-unlink");
+        let canonical_skill_path = temp_directory_path.join("canonical").join("pdf");
+        fs::create_dir_all(&canonical_skill_path).expect("failed to create canonical skill");
+        fs::write(canonical_skill_path.join("SKILL.md"), "# pdf").expect("failed to write SKILL.md");
+        let tool_root_path = temp_directory_path.join("cursor-skills");
+        let link_path = tool_root_path.join("pdf");
+        create_directory_link(&canonical_skill_path, &link_path).expect("failed to create link");
+
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(tool_root_path.clone());
+        let skills = scanner.scan();
+        let linked_skills: Vec<&Skill> = skills.iter().collect();
+
+        let planned_outcomes = unlink_skills(&linked_skills, true, true);
+        assert_eq!(planned_outcomes[0].status, UnlinkStatus::Planned);
+        assert_eq!(planned_outcomes[0].link_target, canonical_skill_path);
+        assert!(read_link_target(&link_path).is_some());
+
+        let materialized_outcomes = unlink_skills(&linked_skills, true, false);
+        assert_eq!(materialized_outcomes[0].status, UnlinkStatus::Materialized);
+        assert!(read_link_target(&link_path).is_none());
+        assert!(link_path.join("SKILL.md").is_file());
+
+        let failed_outcomes = unlink_skills(&linked_skills, false, false);
+        assert_eq!(failed_outcomes[0].status, UnlinkStatus::Failed);
+        assert!(failed_outcomes[0].message.is_some());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
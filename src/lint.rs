@@ -0,0 +1,264 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::finding::{Finding, Rule, Severity};
+use crate::manifest::{split_frontmatter, ManifestError, SkillManifest};
+use crate::skill::Skill;
+
+/// `name` 字段的最大长度（Agent Skills 规范）
+const MAX_NAME_CHAR_COUNT: usize = 64;
+/// `description` 字段的最大长度（Agent Skills 规范）
+const MAX_DESCRIPTION_CHAR_COUNT: usize = 1024;
+
+/// 规范允许的顶层 frontmatter 字段
+const KNOWN_FRONTMATTER_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "compatibility",
+    "allowed-tools",
+    "metadata",
+    "version",
+];
+
+pub const MISSING_FRONTMATTER: Rule = Rule {
+    id: "missing-frontmatter",
+    severity: Severity::Error,
+    summary: "SKILL.md 必须以 YAML frontmatter 开头",
+};
+pub const INVALID_FRONTMATTER: Rule = Rule {
+    id: "invalid-frontmatter",
+    severity: Severity::Error,
+    summary: "frontmatter 必须是合法的 YAML 映射",
+};
+pub const MISSING_NAME: Rule = Rule {
+    id: "missing-name",
+    severity: Severity::Error,
+    summary: "frontmatter 必须包含非空的 name",
+};
+pub const MISSING_DESCRIPTION: Rule = Rule {
+    id: "missing-description",
+    severity: Severity::Error,
+    summary: "frontmatter 必须包含非空的 description",
+};
+pub const NAME_MISMATCH: Rule = Rule {
+    id: "name-mismatch",
+    severity: Severity::Error,
+    summary: "name 必须与 skill 目录名一致",
+};
+pub const INVALID_NAME: Rule = Rule {
+    id: "invalid-name",
+    severity: Severity::Error,
+    summary: "name 只能包含小写字母、数字和单个连字符，且不超过 64 个字符",
+};
+pub const DESCRIPTION_TOO_LONG: Rule = Rule {
+    id: "description-too-long",
+    severity: Severity::Error,
+    summary: "description 不能超过 1024 个字符",
+};
+pub const UNKNOWN_KEY: Rule = Rule {
+    id: "unknown-key",
+    severity: Severity::Warning,
+    summary: "frontmatter 包含规范未定义的顶层字段",
+};
+
+/// 所有 lint 规则
+pub const RULES: &[Rule] = &[
+    MISSING_FRONTMATTER,
+    INVALID_FRONTMATTER,
+    MISSING_NAME,
+    MISSING_DESCRIPTION,
+    NAME_MISMATCH,
+    INVALID_NAME,
+    DESCRIPTION_TOO_LONG,
+    UNKNOWN_KEY,
+];
+
+/// 检查一组 skills，返回所有问题
+pub fn lint_skills(skills: &[Skill]) -> Vec<Finding> {
+    skills.iter().flat_map(lint_skill).collect()
+}
+
+/// 检查单个 skill 的 SKILL.md
+pub fn lint_skill(skill: &Skill) -> Vec<Finding> {
+    let skill_markdown_path = skill.path.join("SKILL.md");
+    let mut findings = match fs::read_to_string(&skill_markdown_path) {
+        Ok(content) => lint_skill_markdown(&content, &skill.name),
+        Err(e) => vec![Finding::new(
+            &MISSING_FRONTMATTER,
+            format!("无法读取 SKILL.md: {}", e),
+            skill_markdown_path.clone(),
+        )],
+    };
+
+    for finding in &mut findings {
+        finding.path = skill_markdown_path.clone();
+        finding.skill = skill.name.clone();
+        finding.tool = skill.tool.clone();
+    }
+    findings
+}
+
+/// 检查 SKILL.md 内容；返回的 Finding 尚未填充文件路径
+fn lint_skill_markdown(content: &str, directory_name: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let report = |rule: &Rule, message: String, line: usize| {
+        Finding::new(rule, message, PathBuf::new()).at(line, 1)
+    };
+
+    let frontmatter = match split_frontmatter(content) {
+        Ok(frontmatter) => frontmatter,
+        Err(e) => {
+            findings.push(report(&MISSING_FRONTMATTER, e.to_string(), 1));
+            return findings;
+        }
+    };
+
+    let manifest = match SkillManifest::from_yaml(frontmatter.yaml, frontmatter.yaml_first_line) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            let line = match &e {
+                ManifestError::InvalidYaml { line: Some(line), .. } => *line,
+                _ => frontmatter.yaml_first_line,
+            };
+            findings.push(report(&INVALID_FRONTMATTER, e.to_string(), line));
+            return findings;
+        }
+    };
+
+    let key_line = |key: &str| {
+        frontmatter_key_line(frontmatter.yaml, key)
+            .map(|offset| frontmatter.yaml_first_line + offset)
+            .unwrap_or(1)
+    };
+
+    match manifest.name.as_deref().filter(|name| !name.is_empty()) {
+        None => findings.push(report(&MISSING_NAME, "缺少 name 字段或为空".to_string(), key_line("name"))),
+        Some(name) => {
+            if !is_valid_skill_name(name) {
+                findings.push(report(
+                    &INVALID_NAME,
+                    format!("name `{}` 不符合小写字母/数字/连字符规则（最多 {} 个字符）", name, MAX_NAME_CHAR_COUNT),
+                    key_line("name"),
+                ));
+            }
+            if name != directory_name {
+                findings.push(report(
+                    &NAME_MISMATCH,
+                    format!("name `{}` 与目录名 `{}` 不一致", name, directory_name),
+                    key_line("name"),
+                ));
+            }
+        }
+    }
+
+    match manifest.description.as_deref().filter(|description| !description.is_empty()) {
+        None => findings.push(report(
+            &MISSING_DESCRIPTION,
+            "缺少 description 字段或为空".to_string(),
+            key_line("description"),
+        )),
+        Some(description) => {
+            let description_char_count = description.chars().count();
+            if description_char_count > MAX_DESCRIPTION_CHAR_COUNT {
+                findings.push(report(
+                    &DESCRIPTION_TOO_LONG,
+                    format!(
+                        "description 长度为 {} 个字符，超过上限 {}",
+                        description_char_count, MAX_DESCRIPTION_CHAR_COUNT
+                    ),
+                    key_line("description"),
+                ));
+            }
+        }
+    }
+
+    for key in manifest.raw.keys() {
+        let key = match key.as_str() {
+            Some(key) => key,
+            None => continue,
+        };
+        if !KNOWN_FRONTMATTER_KEYS.contains(&key) {
+            findings.push(report(&UNKNOWN_KEY, format!("未知的顶层字段 `{}`", key), key_line(key)));
+        }
+    }
+
+    findings
+}
+
+/// 校验 skill 名称：1-64 个字符，仅含小写字母、数字和连字符，连字符不能出现在首尾或连续出现
+pub fn is_valid_skill_name(name: &str) -> bool {
+    let name_char_count = name.chars().count();
+    if name_char_count == 0 || name_char_count > MAX_NAME_CHAR_COUNT {
+        return false;
+    }
+    if name.starts_with('-') || name.ends_with('-') || name.contains("--") {
+        return false;
+    }
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// 查找顶层键在 YAML 文本中的行偏移（从 0 开始）
+fn frontmatter_key_line(yaml: &str, key: &str) -> Option<usize> {
+    yaml.lines().position(|line| {
+        if line.starts_with([' ', '\t']) {
+            return false;
+        }
+        line.trim_start_matches(['"', '\''])
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start_matches(['"', '\'']).trim_start().starts_with(':'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule_ids(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|finding| finding.rule_id).collect()
+    }
+
+    #[test]
+    fn lint_accepts_valid_skill() {
+        let content = "---\nname: dom-cleanup\ndescription: Simplify DOM\nlicense: MIT\n---\n# Body\n";
+
+        assert!(lint_skill_markdown(content, "dom-cleanup").is_empty());
+    }
+
+    #[test]
+    fn lint_reports_name_problems_with_line_positions() {
+        let content = "---\ndescription: d\nname: Dom_Cleanup\nauthor: me\n---\n";
+
+        let findings = lint_skill_markdown(content, "dom-cleanup");
+
+        assert_eq!(rule_ids(&findings), vec!["invalid-name", "name-mismatch", "unknown-key"]);
+        assert_eq!(findings[0].line, Some(3));
+        assert_eq!(findings[2].line, Some(4));
+    }
+
+    #[test]
+    fn lint_reports_missing_frontmatter_and_fields() {
+        assert_eq!(
+            rule_ids(&lint_skill_markdown("# Title\n", "x")),
+            vec!["missing-frontmatter"]
+        );
+        assert_eq!(
+            rule_ids(&lint_skill_markdown("---\nname: x\ndescription: \"\"\n---\n", "x")),
+            vec!["missing-description"]
+        );
+
+        let long_description = "a".repeat(MAX_DESCRIPTION_CHAR_COUNT + 1);
+        let content = format!("---\nname: x\ndescription: {}\n---\n", long_description);
+        assert_eq!(rule_ids(&lint_skill_markdown(&content, "x")), vec!["description-too-long"]);
+    }
+
+    #[test]
+    fn is_valid_skill_name_enforces_lowercase_hyphen_rule() {
+        assert!(is_valid_skill_name("pdf-tools-2"));
+        assert!(!is_valid_skill_name("PDF"));
+        assert!(!is_valid_skill_name("-pdf"));
+        assert!(!is_valid_skill_name("pdf--tools"));
+        assert!(!is_valid_skill_name(&"a".repeat(MAX_NAME_CHAR_COUNT + 1)));
+    }
+}
@@ -1,12 +1,34 @@
-mod skill;
-mod scanner;
+mod config;
+mod output;
 mod ui;
 
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
-use clap::Parser;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use clap::{ArgGroup, Args, Parser, Subcommand};
 use console::style;
 
-use scanner::Scanner;
+use skills_scanner::artifact::ArtifactKind;
+use skills_scanner::cache::{self, ScanCache};
+use skills_scanner::install::{ConflictPolicy, InstallMethod, InstallScope};
+use skills_scanner::mcp_server::McpStdioServer;
+use skills_scanner::watch::SkillWatcher;
+use skills_scanner::web::{self, DashboardApi, DashboardServer};
+use skills_scanner::skill::SortKey;
+use skills_scanner::trash::{DeleteMode, Trash};
+use skills_scanner::{audit, delete, dupes, finding, install, link, lint, mcp, resolve, scanner, skill, sync, trash};
+use skills_scanner::{ScanOptions, Scanner, ToolRegistry};
+
+use config::{CliOverrides, Settings};
+use output::OutputFormat;
+
+/// `delete` 子命令：没有任何 skill 匹配
+const EXIT_NOTHING_MATCHED: u8 = 3;
+/// `delete` 子命令：部分或全部删除失败
+const EXIT_PARTIAL_FAILURE: u8 = 4;
 
 /// 扫描并管理本地 AI 工具的 skills
 #[derive(Parser)]
@@ -14,52 +36,487 @@ use scanner::Scanner;
 #[command(version = "0.1.0")]
 #[command(about = "扫描并管理本地 AI 工具的 skills", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// 自定义扫描目录，可多次指定
-    #[arg(short, long, value_name = "DIR")]
+    #[arg(short, long, value_name = "DIR", global = true)]
     path: Option<Vec<PathBuf>>,
 
     /// 仅列出 skills，不进入交互模式
     #[arg(short, long)]
     list: bool,
+
+    /// 输出格式，非 text 格式隐含 --list（默认 text，可在配置文件中修改）
+    #[arg(long, value_enum, global = true)]
+    format: Option<OutputFormat>,
+
+    /// 列表排序方式（默认保持扫描顺序，可在配置文件中修改）
+    #[arg(long, value_enum, global = true)]
+    sort: Option<SortKey>,
+
+    /// 并行扫描的线程数（默认为 CPU 核数，可在配置文件中修改）
+    #[arg(long, value_name = "N", global = true)]
+    threads: Option<NonZeroUsize>,
+
+    /// 在扫描根目录下查找 skill 的最大深度（默认 1，即只查找直接子目录；注册表条目可单独指定）
+    #[arg(long, value_name = "N", global = true)]
+    max_depth: Option<usize>,
+
+    /// 查找时跳过的目录名（如 node_modules、.git），可多次指定
+    #[arg(long = "ignore-dir", value_name = "NAME", global = true)]
+    ignore_dirs: Vec<String>,
+
+    /// 只列出这些类别（skill、cursor-rule、agents-md 等），可多次指定；默认列出所有类别
+    #[arg(long = "kind", value_enum, value_name = "KIND", global = true)]
+    kinds: Vec<ArtifactKind>,
+
+    /// 使用配置文件中的命名 profile
+    #[arg(long, value_name = "NAME", global = true)]
+    profile: Option<String>,
+
+    /// 永久删除，而不是移入回收站
+    #[arg(long, global = true)]
+    permanent: bool,
+
+    /// 不读取也不更新扫描缓存，重新读取每个 skill
+    #[arg(long, global = true)]
+    no_cache: bool,
 }
 
-fn main() {
+#[derive(Subcommand)]
+enum Command {
+    /// 按 Agent Skills 规范检查 skills，发现错误时以非零状态退出
+    Lint {
+        /// 要检查的目录（skills 根目录或单个 skill 目录）；缺省时检查所有默认扫描目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+    /// 审计 skill 目录中的脚本、可疑命令、硬编码地址、隐藏字符与提示注入
+    Audit {
+        /// 要审计的目录（skills 根目录或单个 skill 目录）；缺省时审计所有默认扫描目录
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+    /// 列出工具注册表（内置默认值与用户 tools.toml 合并后的结果）
+    Tools,
+    /// 按条件非交互地删除 skills（默认移入回收站）
+    #[command(group(
+        ArgGroup::new("selector")
+            .required(true)
+            .multiple(true)
+            .args(["names", "tools", "path_prefixes"])
+    ))]
+    Delete {
+        /// 按 skill 名称匹配（glob，不区分大小写），可多次指定
+        #[arg(long = "name", value_name = "GLOB")]
+        names: Vec<String>,
+        /// 按工具匹配：工具名中的单词（如 codex）或 glob，不区分大小写，可多次指定
+        #[arg(long = "tool", value_name = "TOOL")]
+        tools: Vec<String>,
+        /// 只删除位于该目录下的 skills，可多次指定
+        #[arg(long = "path-prefix", value_name = "DIR")]
+        path_prefixes: Vec<PathBuf>,
+        /// 只显示将要删除的内容，不做任何修改
+        #[arg(long)]
+        dry_run: bool,
+        /// 跳过确认提示
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// 将 skill 复制到其他工具的 skills 目录
+    Install(InstallArgs),
+    /// 在其他工具的 skills 目录中创建指向同一规范副本的链接
+    Link(InstallArgs),
+    /// 删除 skill 的链接（不影响规范副本）
+    #[command(group(ArgGroup::new("selector").required(true).multiple(true).args(["skill", "tools"])))]
+    Unlink {
+        /// skill 名称（glob，不区分大小写）
+        #[arg(value_name = "SKILL")]
+        skill: Option<String>,
+        /// 只处理这些工具中的链接：工具名中的单词或 glob，不区分大小写，可多次指定
+        #[arg(long = "tool", value_name = "TOOL")]
+        tools: Vec<String>,
+        /// 删除链接后在原位置保留一份独立副本
+        #[arg(long)]
+        copy: bool,
+        /// 只显示将要处理的链接，不做任何修改
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 查找目标已不存在的链接，并重新指向扫描到的同名规范副本
+    RepairLinks {
+        /// 删除无法修复的链接
+        #[arg(long)]
+        prune: bool,
+        /// 只显示修复计划，不做任何修改
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// 按各工具的优先级规则列出实际生效的 skills，并标出被遮蔽的同名副本
+    Resolve {
+        /// 只显示这些工具：工具名中的单词或 glob，不区分大小写，可多次指定
+        #[arg(long = "tool", value_name = "TOOL")]
+        tools: Vec<String>,
+    },
+    /// 用一份副本覆盖同一 skill 在其他工具中内容已分叉的副本
+    Sync {
+        /// skill 名称（扫描结果中的目录名）
+        #[arg(value_name = "SKILL")]
+        name: String,
+        /// 以该工具中的副本为源（默认取最近修改的副本）
+        #[arg(long = "from", value_name = "TOOL")]
+        from_tool: Option<String>,
+        /// 只显示同步计划，不做任何修改
+        #[arg(long)]
+        dry_run: bool,
+        /// 跳过确认提示
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// 比较各工具中 skills 的内容，列出完全相同、同名但已分叉以及改名后内容相同的副本
+    Dupes,
+    /// 持续监视扫描目录，实时报告 skills 的新增、修改与删除（支持 text / ndjson）
+    Watch,
+    /// 列出各工具 MCP 配置文件中的服务器（环境变量与请求头的取值不会输出）
+    Mcp,
+    /// 作为 MCP 服务器通过 stdio 提供 list_skills、get_skill、search_skills 工具
+    McpServe,
+    /// 启动本地 HTTP 服务，提供 JSON API 与只读网页仪表盘
+    Serve {
+        /// 监听端口
+        #[arg(long, default_value_t = web::DEFAULT_PORT)]
+        port: u16,
+        /// 监听地址；默认只接受本机访问
+        #[arg(long, default_value = "127.0.0.1")]
+        host: IpAddr,
+    },
+    /// 管理回收站中被删除的 skills
+    Trash {
+        #[command(subcommand)]
+        action: TrashCommand,
+    },
+    /// 管理扫描缓存
+    Cache {
+        #[command(subcommand)]
+        action: CacheCommand,
+    },
+}
+
+/// `install` / `link` 子命令的参数
+#[derive(Args)]
+struct InstallArgs {
+    /// skill 名称（扫描结果中的目录名）或 skill 目录路径
+    #[arg(value_name = "SKILL")]
+    skill: String,
+    /// 目标工具，支持完整名称或唯一片段（如 cursor、codex），可多次指定
+    #[arg(long = "to", value_name = "TOOL", required = true)]
+    targets: Vec<String>,
+    /// 同名 skill 存在于多个工具时，指定来源工具
+    #[arg(long = "from", value_name = "TOOL")]
+    from_tool: Option<String>,
+    /// 安装到用户级目录或当前项目
+    #[arg(long, value_enum, default_value_t = InstallScope::User)]
+    scope: InstallScope,
+    /// 目标位置已存在同名 skill 时的处理方式
+    #[arg(long, value_enum, default_value_t = ConflictPolicy::Skip)]
+    on_conflict: ConflictPolicy,
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// 删除扫描缓存文件
+    Clear,
+}
+
+#[derive(Subcommand)]
+enum TrashCommand {
+    /// 列出回收站中的条目
+    List,
+    /// 将条目恢复到原始位置
+    Restore {
+        /// 条目 ID（见 `trash list`）
+        id: String,
+    },
+    /// 永久删除回收站中的旧条目
+    Purge {
+        /// 只删除早于该时长的条目，如 30d、12h、2w
+        #[arg(long, value_name = "AGE", value_parser = parse_age_arg, required_unless_present = "all")]
+        older_than: Option<Duration>,
+        /// 清空整个回收站
+        #[arg(long, conflicts_with = "older_than")]
+        all: bool,
+    },
+}
+
+fn main() -> ExitCode {
     let cli = Cli::parse();
 
-    // 创建扫描器
-    let mut scanner = Scanner::new();
+    // 合并内置默认值、全局配置、项目配置与命令行参数
+    let settings = match Settings::load(CliOverrides {
+        profile: cli.profile,
+        paths: cli.path.unwrap_or_default(),
+        format: cli.format,
+        sort: cli.sort,
+        threads: cli.threads,
+        max_depth: cli.max_depth,
+        ignore_dirs: cli.ignore_dirs,
+        kinds: cli.kinds,
+        no_cache: cli.no_cache,
+    }) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("配置加载失败: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    // 只有 lint/audit 输出问题报告，其余子命令一律拒绝 sarif
+    let is_findings_command = matches!(cli.command, Some(Command::Lint { .. } | Command::Audit { .. }));
+    if !is_findings_command {
+        if let Err(e) = settings.format.ensure_not_sarif() {
+            eprintln!("{}", e);
+            return ExitCode::from(2);
+        }
+    }
+
+    match cli.command {
+        Some(Command::Lint { paths }) => {
+            run_findings_command(&settings, &paths, lint::RULES, lint::lint_skills)
+        }
+        Some(Command::Audit { paths }) => {
+            run_findings_command(&settings, &paths, audit::RULES, |skills| {
+                audit::audit_skills(skills, &settings.scan_options)
+            })
+        }
+        Some(Command::Tools) => run_tools(settings.format),
+        Some(Command::Delete {
+            names,
+            tools,
+            path_prefixes,
+            dry_run,
+            yes,
+        }) => match delete::SkillSelector::new(&names, &tools, &path_prefixes) {
+            Ok(selector) => run_delete(&settings, &selector, dry_run, yes, cli.permanent),
+            Err(e) => {
+                eprintln!("无效的 glob 模式: {}", e);
+                ExitCode::from(2)
+            }
+        },
+        Some(Command::Install(install_args)) => {
+            run_install(&settings, &install_args, InstallMethod::Copy, cli.permanent)
+        }
+        Some(Command::Link(install_args)) => {
+            run_install(&settings, &install_args, InstallMethod::Link, cli.permanent)
+        }
+        Some(Command::Unlink {
+            skill,
+            tools,
+            copy,
+            dry_run,
+        }) => {
+            let names: Vec<String> = skill.into_iter().collect();
+            match delete::SkillSelector::new(&names, &tools, &[]) {
+                Ok(selector) => run_unlink(&settings, &selector, copy, dry_run),
+                Err(e) => {
+                    eprintln!("无效的 glob 模式: {}", e);
+                    ExitCode::from(2)
+                }
+            }
+        }
+        Some(Command::RepairLinks { prune, dry_run }) => run_repair_links(&settings, prune, dry_run),
+        Some(Command::Resolve { tools }) => match delete::SkillSelector::new(&[], &tools, &[]) {
+            Ok(selector) => run_resolve(&settings, &selector),
+            Err(e) => {
+                eprintln!("无效的 glob 模式: {}", e);
+                ExitCode::from(2)
+            }
+        },
+        Some(Command::Sync {
+            name,
+            from_tool,
+            dry_run,
+            yes,
+        }) => run_sync(&settings, &name, from_tool.as_deref(), dry_run, yes, cli.permanent),
+        Some(Command::Dupes) => run_dupes(&settings),
+        Some(Command::Watch) => run_watch(&settings),
+        Some(Command::Mcp) => run_mcp(&settings),
+        Some(Command::McpServe) => run_mcp_serve(&settings),
+        Some(Command::Serve { port, host }) => run_serve(&settings, SocketAddr::new(host, port)),
+        Some(Command::Trash { action }) => run_trash(settings.format, action),
+        Some(Command::Cache { action }) => run_cache(action),
+        None => run_default(&settings, cli.list, cli.permanent),
+    }
+}
+
+/// 创建扫描器：注册表中的默认路径加上配置与 `--path` 指定的目录
+fn build_scanner(settings: &Settings, registry: &ToolRegistry) -> Scanner {
+    let mut scanner = Scanner::from_registry(registry);
+    for path in &settings.paths {
+        scanner.add_custom_path(path.clone());
+    }
+    apply_scan_options(&mut scanner, settings.scan_options.clone());
+    attach_cache(&mut scanner, settings);
+    scanner
+}
+
+/// 创建只查找 skill 的扫描器，供只适用于 skill 目录的命令使用（忽略 `--kind`）
+fn build_skill_scanner(settings: &Settings, registry: &ToolRegistry) -> Scanner {
+    let mut scanner = build_scanner(settings, registry);
+    apply_scan_options(
+        &mut scanner,
+        ScanOptions {
+            kinds: vec![ArtifactKind::Skill],
+            ..settings.scan_options.clone()
+        },
+    );
+    scanner
+}
 
-    // 添加自定义路径
-    if let Some(paths) = cli.path {
-        for path in paths {
-            scanner.add_custom_path(path);
+/// 设置扫描选项；扫描线程池无法创建时给出警告，改用默认线程池继续扫描
+fn apply_scan_options(scanner: &mut Scanner, options: ScanOptions) {
+    if let Err(e) = scanner.set_options(options) {
+        eprintln!("{} {}，改用默认线程数", style("⚠").yellow(), e);
+    }
+}
+
+/// 交互模式与 `delete` 命令的扫描选项：只查找可删除的类别，并与 `--kind` 取交集
+fn deletable_scan_options(settings: &Settings) -> Option<ScanOptions> {
+    let kinds: Vec<ArtifactKind> = if settings.scan_options.kinds.is_empty() {
+        ArtifactKind::DELETABLE.to_vec()
+    } else {
+        settings
+            .scan_options
+            .kinds
+            .iter()
+            .copied()
+            .filter(|kind| ArtifactKind::DELETABLE.contains(kind))
+            .collect()
+    };
+    if kinds.is_empty() {
+        eprintln!("--kind 中没有可删除的类别（skill、claude-command、claude-agent）");
+        return None;
+    }
+    Some(ScanOptions {
+        kinds,
+        ..settings.scan_options.clone()
+    })
+}
+
+/// 未指定 `--no-cache` 时为扫描器启用默认位置的缓存
+fn attach_cache(scanner: &mut Scanner, settings: &Settings) {
+    if settings.use_cache {
+        if let Some(scan_cache) = ScanCache::open_default() {
+            scanner.set_cache(scan_cache);
         }
     }
+}
 
-    // 显示扫描信息
-    ui::show_scanning_message(scanner.path_count());
+/// 创建只扫描指定目录的扫描器
+fn build_explicit_scanner(settings: &Settings, paths: &[PathBuf]) -> Scanner {
+    let mut scanner = Scanner::empty();
+    for path in paths {
+        if !path.is_dir() {
+            eprintln!("{} 目录不存在: {}", style("⚠").yellow(), path.display());
+        }
+        scanner.add_custom_path(path.clone());
+    }
+    apply_scan_options(&mut scanner, settings.scan_options.clone());
+    attach_cache(&mut scanner, settings);
+    scanner
+}
 
-    // 执行扫描
+/// 扫描并按配置排序
+fn scan_sorted(scanner: &Scanner, sort_key: SortKey) -> Vec<skill::Skill> {
     let mut skills = scanner.scan();
+    skill::sort_skills(&mut skills, sort_key);
+    skills
+}
+
+/// 根据 `--permanent` 选择删除方式
+fn build_delete_mode(permanent: bool) -> Option<DeleteMode> {
+    if permanent {
+        return Some(DeleteMode::Permanent);
+    }
+    let trash = Trash::open_default();
+    if trash.is_none() {
+        eprintln!("无法确定回收站位置，如需直接删除请使用 --permanent");
+    }
+    trash.map(DeleteMode::Trash)
+}
+
+/// 无子命令：列出或进入交互模式
+fn run_default(settings: &Settings, list: bool, permanent: bool) -> ExitCode {
+    let format = settings.format;
+    // 创建扫描器
+    let registry = load_registry();
+    let mut scanner = build_scanner(settings, &registry);
 
-    if cli.list {
+    // 显示扫描信息（机器可读格式下保持 stdout 干净）
+    if !format.is_machine_readable() {
+        ui::show_scanning_message(scanner.path_count());
+    }
+
+    if format == OutputFormat::Ndjson && settings.sort == SortKey::Scan {
+        // 流式输出，不等待整个扫描结束
+        if let Err(e) = output::stream_skills_ndjson(&scanner) {
+            return report_output_error(&e);
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    // 执行扫描
+    let scan_started_at = Instant::now();
+    let mut skills = scan_sorted(&scanner, settings.sort);
+    let scan_elapsed = scan_started_at.elapsed();
+
+    if list || format.is_machine_readable() {
         // 仅列出模式
-        ui::display_skills(&skills);
-        return;
+        if let Err(e) = output::print_skills(format, scanner.roots(), &skills, scan_elapsed) {
+            return report_output_error(&e);
+        }
+        return ExitCode::SUCCESS;
     }
 
+    let Some(delete_mode) = build_delete_mode(permanent) else {
+        return ExitCode::from(2);
+    };
+
+    // 交互模式管理 skill 与 Claude Code 命令、子代理，规则与指令文件仅在列表中显示
+    let Some(deletable_options) = deletable_scan_options(settings) else {
+        return ExitCode::from(2);
+    };
+    skills.retain(|skill| deletable_options.kinds.contains(&skill.kind));
+    apply_scan_options(&mut scanner, deletable_options.clone());
+
     // 交互模式循环
     loop {
         match ui::show_main_menu(skills.len()) {
             Ok(ui::MainMenuAction::BrowseAll) => {
                 let all_indices: Vec<usize> = (0..skills.len()).collect();
-                match ui::interactive_select_and_delete(&skills, &all_indices) {
+                match ui::interactive_select_and_delete(&skills, &all_indices, &delete_mode) {
                     Ok(true) => {
                         println!(
                             "\n{} 正在重新扫描...\n",
                             style("🔍").cyan()
                         );
-                        skills = scanner.scan();
+                        skills = scan_sorted(&scanner, settings.sort);
+                    }
+                    Ok(false) => {}
+                    Err(e) => eprintln!("操作出错: {}", e),
+                }
+            }
+            Ok(ui::MainMenuAction::Install) => {
+                match ui::interactive_install(&skills, &registry, &delete_mode) {
+                    Ok(true) => {
+                        println!(
+                            "\n{} 正在重新扫描...\n",
+                            style("🔍").cyan()
+                        );
+                        // 安装可能创建了新的工具目录，需要重新收集扫描根目录
+                        scanner = build_scanner(settings, &registry);
+                        apply_scan_options(&mut scanner, deletable_options.clone());
+                        skills = scan_sorted(&scanner, settings.sort);
                     }
                     Ok(false) => {}
                     Err(e) => eprintln!("操作出错: {}", e),
@@ -75,4 +532,586 @@ fn main() {
             }
         }
     }
+
+    ExitCode::SUCCESS
+}
+
+/// `lint`/`audit` 子命令：存在错误级别问题时返回失败
+fn run_findings_command(
+    settings: &Settings,
+    paths: &[PathBuf],
+    rules: &[finding::Rule],
+    check: impl Fn(&[skill::Skill]) -> Vec<finding::Finding>,
+) -> ExitCode {
+    let format = settings.format;
+    let scanner = if paths.is_empty() {
+        build_skill_scanner(settings, &load_registry())
+    } else {
+        build_explicit_scanner(settings, paths)
+    };
+
+    if !format.is_machine_readable() {
+        ui::show_scanning_message(scanner.path_count());
+    }
+
+    let skills = scan_sorted(&scanner, settings.sort);
+    let findings = check(&skills);
+
+    if let Err(e) = output::print_findings(format, rules, &findings) {
+        return report_output_error(&e);
+    }
+
+    if findings.iter().any(|finding| finding.is_error()) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `tools` 子命令：显示合并后的工具注册表
+fn run_tools(format: OutputFormat) -> ExitCode {
+    let registry = match ToolRegistry::load() {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("工具注册表加载失败: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    warn_unresolved_entries(&registry);
+
+    if let Err(e) = output::print_tools(format, &registry) {
+        return report_output_error(&e);
+    }
+    ExitCode::SUCCESS
+}
+
+/// `delete` 子命令：删除与条件匹配的 skills
+///
+/// 退出码：0 成功，1 取消，3 没有匹配项，4 存在删除失败。
+fn run_delete(
+    settings: &Settings,
+    selector: &delete::SkillSelector,
+    dry_run: bool,
+    yes: bool,
+    permanent: bool,
+) -> ExitCode {
+    let format = settings.format;
+    let Some(delete_mode) = build_delete_mode(permanent) else {
+        return ExitCode::from(2);
+    };
+
+    let Some(deletable_options) = deletable_scan_options(settings) else {
+        return ExitCode::from(2);
+    };
+    let mut scanner = build_scanner(settings, &load_registry());
+    apply_scan_options(&mut scanner, deletable_options);
+    let skills = scan_sorted(&scanner, settings.sort);
+    let selected_skills = delete::dedupe_by_path(skills.iter().filter(|skill| selector.matches(skill)));
+
+    if selected_skills.is_empty() {
+        eprintln!("{} 没有匹配的 skills", style("⚠").yellow());
+        return ExitCode::from(EXIT_NOTHING_MATCHED);
+    }
+
+    if !format.is_machine_readable() {
+        ui::display_delete_plan(&selected_skills, &delete_mode);
+    }
+
+    if !dry_run && !yes {
+        if !console::user_attended() {
+            eprintln!("当前不是交互终端，请使用 --yes 确认删除或 --dry-run 预览");
+            return ExitCode::FAILURE;
+        }
+        match ui::confirm("确认删除") {
+            Ok(true) => {}
+            Ok(false) => {
+                eprintln!("已取消删除操作");
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("操作出错: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let delete_outcomes = delete::delete_selected(&selected_skills, &delete_mode, dry_run);
+    if dry_run && !format.is_machine_readable() {
+        println!("{}", style("dry-run: 未做任何修改").dim());
+    } else if let Err(e) =
+        output::print_delete_outcomes(format, &delete_outcomes, dry_run, delete_mode.is_permanent())
+    {
+        return report_output_error(&e);
+    }
+
+    if delete_outcomes
+        .iter()
+        .any(|delete_outcome| delete_outcome.status == delete::DeleteStatus::Failed)
+    {
+        ExitCode::from(EXIT_PARTIAL_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `install` / `link` 子命令：复制或链接 skill 到一个或多个工具，任一目标失败时返回失败
+fn run_install(
+    settings: &Settings,
+    install_args: &InstallArgs,
+    install_method: InstallMethod,
+    permanent: bool,
+) -> ExitCode {
+    let format = settings.format;
+    let Some(delete_mode) = build_delete_mode(permanent) else {
+        return ExitCode::from(2);
+    };
+    let registry = load_registry();
+
+    let scanner = build_skill_scanner(settings, &registry);
+    let skills = scan_sorted(&scanner, settings.sort);
+    let source_skill =
+        match install::find_source_skill(&skills, &install_args.skill, install_args.from_tool.as_deref()) {
+            Ok(source_skill) => source_skill,
+            Err(e) => {
+                eprintln!("{} {}", style("✗").red(), e);
+                return ExitCode::FAILURE;
+            }
+        };
+
+    let mut install_targets = Vec::new();
+    for target_query in &install_args.targets {
+        match install::resolve_install_target(&registry, target_query, install_args.scope) {
+            Ok(install_target) => install_targets.push(install_target),
+            Err(e) => {
+                eprintln!("{} {}", style("✗").red(), e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let install_outcomes: Vec<_> = install_targets
+        .iter()
+        .map(|install_target| {
+            install::install_skill(
+                &source_skill,
+                install_target,
+                install_method,
+                install_args.on_conflict,
+                &delete_mode,
+            )
+        })
+        .collect();
+    if let Err(e) = output::print_install_outcomes(format, &source_skill, &install_outcomes) {
+        return report_output_error(&e);
+    }
+
+    if install_outcomes
+        .iter()
+        .any(|install_outcome| install_outcome.status == install::InstallStatus::Failed)
+    {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `unlink` 子命令：删除匹配的链接，可选保留独立副本
+fn run_unlink(settings: &Settings, selector: &delete::SkillSelector, copy: bool, dry_run: bool) -> ExitCode {
+    let scanner = build_skill_scanner(settings, &load_registry());
+    let skills = scan_sorted(&scanner, settings.sort);
+    let linked_skills = delete::dedupe_by_path(
+        skills
+            .iter()
+            .filter(|skill| skill.link_target.is_some() && selector.matches(skill)),
+    );
+
+    if linked_skills.is_empty() {
+        eprintln!("{} 没有匹配的链接", style("⚠").yellow());
+        return ExitCode::from(EXIT_NOTHING_MATCHED);
+    }
+
+    let unlink_outcomes = link::unlink_skills(&linked_skills, copy, dry_run);
+
+    if let Err(e) = output::print_unlink_outcomes(settings.format, &unlink_outcomes) {
+        return report_output_error(&e);
+    }
+
+    if unlink_outcomes
+        .iter()
+        .any(|unlink_outcome| unlink_outcome.status == link::UnlinkStatus::Failed)
+    {
+        ExitCode::from(EXIT_PARTIAL_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `repair-links` 子命令：修复扫描目录中的失效链接
+fn run_repair_links(settings: &Settings, prune: bool, dry_run: bool) -> ExitCode {
+    let format = settings.format;
+    let scanner = build_skill_scanner(settings, &load_registry());
+    let skills = scan_sorted(&scanner, settings.sort);
+    let broken_links = link::find_broken_links(&scanner);
+    let repair_outcomes = link::repair_broken_links(broken_links, &skills, dry_run, prune);
+
+    if let Err(e) = output::print_repair_outcomes(format, &repair_outcomes) {
+        return report_output_error(&e);
+    }
+
+    if repair_outcomes.iter().any(|repair_outcome| {
+        matches!(
+            repair_outcome.status,
+            link::RepairStatus::Failed | link::RepairStatus::Unresolved
+        )
+    }) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `resolve` 子命令：按工具族应用优先级规则
+fn run_resolve(settings: &Settings, selector: &delete::SkillSelector) -> ExitCode {
+    let format = settings.format;
+    let scanner = build_skill_scanner(settings, &load_registry());
+    let skills: Vec<_> = scanner
+        .scan()
+        .into_iter()
+        .filter(|skill| selector.matches(skill))
+        .collect();
+    let tool_resolutions = resolve::resolve_effective_skills(&skills, &scanner::workspace_search_paths());
+
+    if let Err(e) = output::print_resolution(format, &tool_resolutions) {
+        return report_output_error(&e);
+    }
+    ExitCode::SUCCESS
+}
+
+/// `sync` 子命令：选出源副本，展示文件级计划，确认后覆盖其余副本
+fn run_sync(
+    settings: &Settings,
+    name: &str,
+    from_tool: Option<&str>,
+    dry_run: bool,
+    yes: bool,
+    permanent: bool,
+) -> ExitCode {
+    let format = settings.format;
+    let Some(delete_mode) = build_delete_mode(permanent) else {
+        return ExitCode::from(2);
+    };
+
+    let scanner = build_skill_scanner(settings, &load_registry());
+    let skills = scan_sorted(&scanner, settings.sort);
+    let copies = match sync::select_copies(&skills, name) {
+        Ok(copies) => copies,
+        Err(qualified_names) => {
+            eprintln!(
+                "{} 多个分类下都有名为 `{}` 的 skill，请用 `分类/名称` 指定: {}",
+                style("✗").red(),
+                name,
+                qualified_names.join(", ")
+            );
+            return ExitCode::from(2);
+        }
+    };
+    if copies.is_empty() {
+        eprintln!("{} 找不到 skill `{}`", style("⚠").yellow(), name);
+        return ExitCode::from(EXIT_NOTHING_MATCHED);
+    }
+
+    let source_skill = match from_tool {
+        Some(from_tool) => match install::find_source_skill(&skills, name, Some(from_tool)) {
+            Ok(source_skill) => source_skill,
+            Err(e) => {
+                eprintln!("{} {}", style("✗").red(), e);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => match sync::newest_copy(&copies) {
+            Some(source_skill) => source_skill.clone(),
+            None => return ExitCode::from(EXIT_NOTHING_MATCHED),
+        },
+    };
+
+    let planned_outcomes = match sync::plan_sync(&source_skill, &copies) {
+        Ok(planned_outcomes) => planned_outcomes,
+        Err(e) => {
+            eprintln!("{} 无法读取源 {}: {}", style("✗").red(), source_skill.path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let has_pending_copies = planned_outcomes
+        .iter()
+        .any(|sync_outcome| sync_outcome.status == sync::SyncStatus::Planned);
+
+    if !format.is_machine_readable() {
+        ui::display_sync_plan(&source_skill, &planned_outcomes, &delete_mode);
+    }
+
+    if has_pending_copies && !dry_run && !yes {
+        if !console::user_attended() {
+            eprintln!("当前不是交互终端，请使用 --yes 确认同步或 --dry-run 预览");
+            return ExitCode::FAILURE;
+        }
+        match ui::confirm("确认同步") {
+            Ok(true) => {}
+            Ok(false) => {
+                eprintln!("已取消同步操作");
+                return ExitCode::FAILURE;
+            }
+            Err(e) => {
+                eprintln!("操作出错: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let sync_outcomes = if dry_run {
+        planned_outcomes
+    } else {
+        sync::apply_sync(&source_skill, planned_outcomes, &delete_mode)
+    };
+    if dry_run && !format.is_machine_readable() {
+        println!("{}", style("dry-run: 未做任何修改").dim());
+    } else if let Err(e) = output::print_sync_outcomes(format, &source_skill, &sync_outcomes, dry_run) {
+        return report_output_error(&e);
+    }
+
+    if sync_outcomes
+        .iter()
+        .any(|sync_outcome| sync_outcome.status == sync::SyncStatus::Failed)
+    {
+        ExitCode::from(EXIT_PARTIAL_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `dupes` 子命令：按内容哈希查找重复与分叉的 skills
+fn run_dupes(settings: &Settings) -> ExitCode {
+    let format = settings.format;
+    let scanner = build_skill_scanner(settings, &load_registry());
+    let skills = scan_sorted(&scanner, settings.sort);
+    let dupe_report = dupes::find_dupes(&skills);
+
+    if let Err(e) = output::print_dupes(format, &dupe_report) {
+        return report_output_error(&e);
+    }
+    ExitCode::SUCCESS
+}
+
+/// `watch` 子命令：监视扫描目录直到被中断
+fn run_watch(settings: &Settings) -> ExitCode {
+    let format = settings.format;
+    if !matches!(format, OutputFormat::Text | OutputFormat::Ndjson) {
+        eprintln!("watch 仅支持 text 与 ndjson 格式");
+        return ExitCode::from(2);
+    }
+
+    let scanner = build_scanner(settings, &load_registry());
+    let mut skill_watcher = match SkillWatcher::new(scanner) {
+        Ok(skill_watcher) => skill_watcher,
+        Err(e) => {
+            eprintln!("{} {}", style("✗").red(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !format.is_machine_readable() {
+        ui::show_watching_message(
+            skill_watcher.scanner().path_count(),
+            skill_watcher.skills().len(),
+            skill_watcher.scanner().missing_roots().len(),
+        );
+    }
+
+    loop {
+        let watch_events = match skill_watcher.poll_events(Duration::from_secs(60)) {
+            Ok(watch_events) => watch_events,
+            Err(e) => {
+                eprintln!("{} {}", style("✗").red(), e);
+                return ExitCode::FAILURE;
+            }
+        };
+        if let Err(e) = output::print_watch_events(format, &watch_events) {
+            // 下游管道关闭时正常退出，其他输出错误返回失败
+            return report_output_error(&e);
+        }
+    }
+}
+
+/// 加载工具注册表，失败时回退到内置默认值
+fn load_registry() -> ToolRegistry {
+    let registry = ToolRegistry::load().unwrap_or_else(|e| {
+        eprintln!("工具注册表加载失败，使用内置默认值: {}", e);
+        ToolRegistry::builtin()
+    });
+    warn_unresolved_entries(&registry);
+    registry
+}
+
+/// 提示因环境变量未定义而被跳过的注册表条目
+fn warn_unresolved_entries(registry: &ToolRegistry) {
+    for unresolved_entry in registry.unresolved_entries() {
+        eprintln!("{} {}", style("⚠").yellow(), unresolved_entry);
+    }
+}
+
+/// `mcp` 子命令：列出 MCP 配置文件及其中的服务器；有文件无法解析时返回部分失败
+fn run_mcp(settings: &Settings) -> ExitCode {
+    let format = settings.format;
+    let config_files: Vec<_> = mcp::discover_config_files()
+        .into_iter()
+        .filter(|config_file| !settings.scan_options.is_tool_excluded(&config_file.tool))
+        .collect();
+
+    if let Err(e) = output::print_mcp_config_files(format, &config_files) {
+        return report_output_error(&e);
+    }
+    if config_files.iter().any(|config_file| config_file.error.is_some()) {
+        ExitCode::from(EXIT_PARTIAL_FAILURE)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// `mcp-serve` 子命令：stdout 只用于协议消息，直到 stdin 关闭
+fn run_mcp_serve(settings: &Settings) -> ExitCode {
+    let scanner = build_scanner(settings, &load_registry());
+    let mcp_server = McpStdioServer::new(scanner);
+    match mcp_server.serve(io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("MCP 服务出错: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `serve` 子命令：启动 HTTP 服务直到被中断
+fn run_serve(settings: &Settings, address: SocketAddr) -> ExitCode {
+    let registry = load_registry();
+    let scanner = build_scanner(settings, &registry);
+    let dashboard_server = match DashboardServer::bind(DashboardApi::new(scanner, registry), address) {
+        Ok(dashboard_server) => dashboard_server,
+        Err(e) => {
+            eprintln!("{} 无法监听 {}: {}", style("✗").red(), address, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !address.ip().is_loopback() {
+        eprintln!(
+            "{} 正在监听非本机地址 {}，同一网络中的其他人也能读取所有 skills",
+            style("⚠").yellow(),
+            address.ip()
+        );
+    }
+    let listen_address = dashboard_server.local_address().unwrap_or(address);
+    println!(
+        "\n{} 仪表盘: {}  （Ctrl+C 停止）\n",
+        style("🌐").cyan(),
+        style(format!("http://{}/", listen_address)).green().bold()
+    );
+
+    match dashboard_server.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("HTTP 服务出错: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `cache` 子命令：管理扫描缓存
+fn run_cache(action: CacheCommand) -> ExitCode {
+    let Some(cache_file_path) = cache::default_cache_file_path() else {
+        eprintln!("无法确定缓存位置");
+        return ExitCode::FAILURE;
+    };
+
+    match action {
+        CacheCommand::Clear => match cache::clear_cache_file(&cache_file_path) {
+            Ok(true) => {
+                println!("{} 已清除扫描缓存: {}", style("✓").green(), cache_file_path.display());
+                ExitCode::SUCCESS
+            }
+            Ok(false) => {
+                println!("{} 扫描缓存为空", style("✓").green());
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{} 无法删除 {}: {}", style("✗").red(), cache_file_path.display(), e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+/// `trash` 子命令：列出、恢复或清理回收站
+fn run_trash(format: OutputFormat, action: TrashCommand) -> ExitCode {
+    let Some(trash) = Trash::open_default() else {
+        eprintln!("无法确定回收站位置");
+        return ExitCode::FAILURE;
+    };
+
+    let result = match action {
+        TrashCommand::List => match trash.list() {
+            Ok(trash_listing) => {
+                if let Err(e) = output::print_trash_entries(format, &trash_listing.entries) {
+                    return report_output_error(&e);
+                }
+                Ok(trash_listing.invalid_entries)
+            }
+            Err(e) => Err(e),
+        },
+        TrashCommand::Restore { id } => trash.restore(&id).map(|trash_entry| {
+            println!(
+                "{} 已恢复: {} > {}\n     {}",
+                style("✓").green(),
+                style(&trash_entry.tool).cyan(),
+                style(&trash_entry.name).white(),
+                style(trash_entry.original_path.display()).dim()
+            );
+            Vec::new()
+        }),
+        TrashCommand::Purge { older_than, all: _ } => {
+            // 未指定 --older-than 时即为 --all
+            trash.purge(older_than.unwrap_or_default()).map(|purge_listing| {
+                println!(
+                    "{} 已永久删除 {} 个回收站条目",
+                    style("✓").green(),
+                    style(purge_listing.entries.len()).green().bold()
+                );
+                purge_listing.invalid_entries
+            })
+        }
+    };
+
+    match result {
+        Ok(invalid_entries) if invalid_entries.is_empty() => ExitCode::SUCCESS,
+        Ok(invalid_entries) => {
+            for invalid_entry in &invalid_entries {
+                eprintln!("{} 已跳过: {}", style("⚠").yellow(), invalid_entry);
+            }
+            ExitCode::from(EXIT_PARTIAL_FAILURE)
+        }
+        Err(e) => {
+            eprintln!("{} {}", style("✗").red(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 解析 `--older-than` 参数
+fn parse_age_arg(text: &str) -> Result<Duration, String> {
+    trash::parse_age(text).ok_or_else(|| format!("无法解析时长 `{}`，应为数字加单位 m/h/d/w，如 30d", text))
+}
+
+/// 报告输出错误并返回失败退出码；下游管道提前关闭（如 `| head`）时静默正常退出
+fn report_output_error(error: &std::io::Error) -> ExitCode {
+    if error.kind() == std::io::ErrorKind::BrokenPipe {
+        return ExitCode::SUCCESS;
+    }
+    eprintln!("输出出错: {}", error);
+    ExitCode::FAILURE
 }
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+
+/// frontmatter 的起止分隔线
+const FRONTMATTER_DELIMITER: &str = "---";
+/// YAML 文档结束标记，也可用于关闭 frontmatter
+const FRONTMATTER_END_MARKER: &str = "...";
+
+/// 从 SKILL.md 的 YAML frontmatter 解析出的清单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillManifest {
+    /// `name` 字段
+    pub name: Option<String>,
+    /// `description` 字段
+    pub description: Option<String>,
+    /// `license` 字段
+    pub license: Option<String>,
+    /// `allowed-tools` 字段，兼容空格/逗号分隔字符串与 YAML 列表
+    pub allowed_tools: Vec<String>,
+    /// `version` 字段（缺省时回退到 `metadata.version`）
+    pub version: Option<String>,
+    /// `metadata` 下的任意键值
+    pub metadata: BTreeMap<String, Value>,
+    /// 完整的原始 frontmatter 映射
+    #[serde(rename = "frontmatter")]
+    pub raw: Mapping,
+}
+
+/// 从 Markdown 中切分出的 frontmatter 区块
+#[derive(Debug, Clone, Copy)]
+pub struct Frontmatter<'a> {
+    /// 两条分隔线之间的 YAML 文本
+    pub yaml: &'a str,
+    /// frontmatter 之后的正文
+    pub body: &'a str,
+    /// YAML 文本第一行在原文件中的行号（从 1 开始）
+    pub yaml_first_line: usize,
+}
+
+/// 解析 frontmatter 失败的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestError {
+    /// 文件不以 `---` 开头
+    Missing,
+    /// 找不到结束分隔线
+    Unterminated,
+    /// YAML 语法错误，附带原文件中的行号
+    InvalidYaml { message: String, line: Option<usize> },
+    /// YAML 顶层不是映射
+    NotAMapping,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifestError::Missing => write!(f, "缺少 YAML frontmatter"),
+            ManifestError::Unterminated => write!(f, "frontmatter 缺少结束分隔线 `---`"),
+            ManifestError::InvalidYaml { message, .. } => write!(f, "frontmatter YAML 无效: {}", message),
+            ManifestError::NotAMapping => write!(f, "frontmatter 顶层必须是键值映射"),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+/// 切分 Markdown 开头的 frontmatter 区块
+pub fn split_frontmatter(content: &str) -> Result<Frontmatter<'_>, ManifestError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut lines = content.split_inclusive('\n');
+
+    let opening_line = lines.next().ok_or(ManifestError::Missing)?;
+    if opening_line.trim_end() != FRONTMATTER_DELIMITER {
+        return Err(ManifestError::Missing);
+    }
+
+    let yaml_start = opening_line.len();
+    let mut offset = yaml_start;
+    for line in lines {
+        let trimmed_line = line.trim_end();
+        if trimmed_line == FRONTMATTER_DELIMITER || trimmed_line == FRONTMATTER_END_MARKER {
+            return Ok(Frontmatter {
+                yaml: &content[yaml_start..offset],
+                body: &content[offset + line.len()..],
+                yaml_first_line: 2,
+            });
+        }
+        offset += line.len();
+    }
+
+    Err(ManifestError::Unterminated)
+}
+
+impl SkillManifest {
+    /// 从 SKILL.md 全文解析清单
+    pub fn parse(content: &str) -> Result<Self, ManifestError> {
+        let frontmatter = split_frontmatter(content)?;
+        Self::from_yaml(frontmatter.yaml, frontmatter.yaml_first_line)
+    }
+
+    /// 从 frontmatter 的 YAML 文本解析清单
+    pub fn from_yaml(yaml: &str, yaml_first_line: usize) -> Result<Self, ManifestError> {
+        if yaml.trim().is_empty() {
+            return Ok(Self::default());
+        }
+
+        let document: Value = serde_yaml::from_str(yaml).map_err(|e| ManifestError::InvalidYaml {
+            message: e.to_string(),
+            line: e.location().map(|location| location.line() + yaml_first_line - 1),
+        })?;
+        let raw = match document {
+            Value::Mapping(mapping) => mapping,
+            Value::Null => Mapping::new(),
+            _ => return Err(ManifestError::NotAMapping),
+        };
+
+        let metadata: BTreeMap<String, Value> = match raw.get("metadata") {
+            Some(Value::Mapping(metadata_mapping)) => metadata_mapping
+                .iter()
+                .filter_map(|(key, value)| Some((scalar_to_string(key)?, value.clone())))
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+
+        let version = raw
+            .get("version")
+            .and_then(scalar_to_string)
+            .or_else(|| metadata.get("version").and_then(scalar_to_string));
+
+        Ok(Self {
+            name: raw.get("name").and_then(scalar_to_string),
+            description: raw.get("description").and_then(scalar_to_string),
+            license: raw.get("license").and_then(scalar_to_string),
+            allowed_tools: raw.get("allowed-tools").map(parse_tool_list).unwrap_or_default(),
+            version,
+            metadata,
+            raw,
+        })
+    }
+}
+
+/// 将 YAML 标量转换为去除首尾空白的字符串
+fn scalar_to_string(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(text) => text.trim().to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        _ => return None,
+    };
+    Some(text)
+}
+
+/// 解析工具列表：既支持 YAML 序列，也支持空格或逗号分隔的字符串
+fn parse_tool_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Sequence(items) => items.iter().filter_map(scalar_to_string).collect(),
+        Value::String(text) => {
+            let separator: &[char] = if text.contains(',') { &[','] } else { &[' ', '\t'] };
+            text.split(separator)
+                .map(str::trim)
+                .filter(|tool| !tool.is_empty())
+                .map(str::to_string)
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_block_scalars_and_escaped_quotes() {
+        let content = "---\nname: dom-cleanup\ndescription: >-\n  Simplify bloated DOM\n  structures.\nlicense: \"MIT \\\"Expat\\\"\"\n---\n# Body\n";
+
+        let manifest = SkillManifest::parse(content).expect("failed to parse manifest");
+
+        assert_eq!(manifest.name.as_deref(), Some("dom-cleanup"));
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("Simplify bloated DOM structures.")
+        );
+        assert_eq!(manifest.license.as_deref(), Some("MIT \"Expat\""));
+    }
+
+    #[test]
+    fn parse_collects_allowed_tools_version_and_metadata() {
+        let content = "---\nname: pdf\ndescription: d\nallowed-tools: Bash(git:*) Read\nmetadata:\n  author: me\n  version: 1.2\n---\n";
+
+        let manifest = SkillManifest::parse(content).expect("failed to parse manifest");
+
+        assert_eq!(manifest.allowed_tools, vec!["Bash(git:*)", "Read"]);
+        assert_eq!(manifest.version.as_deref(), Some("1.2"));
+        assert_eq!(manifest.metadata.get("author"), Some(&Value::from("me")));
+        assert!(manifest.raw.get("metadata").is_some());
+    }
+
+    #[test]
+    fn parse_reports_missing_and_invalid_frontmatter() {
+        assert_eq!(SkillManifest::parse("# Title\n").unwrap_err(), ManifestError::Missing);
+        assert_eq!(
+            SkillManifest::parse("---\nname: x\n").unwrap_err(),
+            ManifestError::Unterminated
+        );
+
+        let invalid_yaml_error = SkillManifest::parse("---\nname: x\ndescription: [\n---\n").unwrap_err();
+        assert!(matches!(invalid_yaml_error, ManifestError::InvalidYaml { .. }));
+    }
+}
@@ -0,0 +1,278 @@
+use std::io::{self, BufRead, Write};
+use clap::ValueEnum;
+use serde_json::{json, Value as JsonValue};
+
+use crate::artifact::ArtifactKind;
+use crate::delete::{self, SkillSelector};
+use crate::scanner::Scanner;
+use crate::skill::{Skill, SkillRecord};
+
+/// 客户端未请求受支持的版本时使用的 MCP 协议版本
+pub const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// 可以按客户端请求协商的协议版本
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// `search_skills` 未指定 `limit` 时最多返回的条数
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// JSON-RPC 错误码
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// JSON-RPC 错误响应
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// 工具调用失败的原因，以 `isError` 结果返回给模型而不是协议错误
+struct ToolError(String);
+
+/// 通过 stdio 提供 Model Context Protocol 服务，工具调用时按扫描器重新扫描
+///
+/// 消息为换行分隔的 JSON-RPC 2.0；提供 `list_skills`、`get_skill` 与 `search_skills` 三个工具。
+pub struct McpStdioServer {
+    scanner: Scanner,
+}
+
+impl McpStdioServer {
+    pub fn new(scanner: Scanner) -> Self {
+        Self { scanner }
+    }
+
+    /// 逐行读取请求并写出响应，直到输入结束
+    pub fn serve<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_message(&line) {
+                serde_json::to_writer(&mut writer, &response)?;
+                writeln!(writer)?;
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 处理一条消息；通知（没有 `id` 的请求）不返回响应
+    pub fn handle_message(&self, message: &str) -> Option<JsonValue> {
+        let request: JsonValue = match serde_json::from_str(message) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(JsonValue::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+        let request_id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(JsonValue::as_str) else {
+            return Some(error_response(
+                request_id.unwrap_or(JsonValue::Null),
+                RpcError::new(INVALID_REQUEST, "缺少 method"),
+            ));
+        };
+        // 通知（如 notifications/initialized）无需响应
+        let request_id = request_id?;
+
+        let params = request.get("params").cloned().unwrap_or(JsonValue::Null);
+        let result = match method {
+            "initialize" => Ok(initialize_result(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("未知方法: {}", method))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": request_id, "result": result }),
+            Err(error) => error_response(request_id, error),
+        })
+    }
+
+    fn call_tool(&self, params: &JsonValue) -> Result<JsonValue, RpcError> {
+        let tool_name = params
+            .get("name")
+            .and_then(JsonValue::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "缺少工具名称 name"))?;
+        let arguments = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+        let tool_result = match tool_name {
+            "list_skills" => self.list_skills(&arguments),
+            "get_skill" => self.get_skill(&arguments),
+            "search_skills" => self.search_skills(&arguments),
+            _ => return Err(RpcError::new(INVALID_PARAMS, format!("未知工具: {}", tool_name))),
+        };
+        Ok(match tool_result {
+            Ok(structured_content) => json!({
+                "content": [{ "type": "text", "text": structured_content.to_string() }],
+                "structuredContent": structured_content,
+                "isError": false,
+            }),
+            Err(ToolError(message)) => json!({
+                "content": [{ "type": "text", "text": message }],
+                "isError": true,
+            }),
+        })
+    }
+
+    /// 扫描并按 `tool`、`kind` 参数筛选
+    fn scan_filtered(&self, arguments: &JsonValue) -> Result<Vec<Skill>, ToolError> {
+        let tool_patterns: Vec<String> = string_argument(arguments, "tool").map(str::to_string).into_iter().collect();
+        let selector = SkillSelector::new(&[], &tool_patterns, &[])
+            .map_err(|e| ToolError(format!("无效的工具模式: {}", e)))?;
+        let kind = string_argument(arguments, "kind")
+            .map(|kind_label| {
+                ArtifactKind::from_str(kind_label, true).map_err(|_| ToolError(format!("未知类别: {}", kind_label)))
+            })
+            .transpose()?;
+        Ok(self
+            .scanner
+            .scan()
+            .into_iter()
+            .filter(|skill| selector.matches(skill) && kind.is_none_or(|kind| skill.kind == kind))
+            .collect())
+    }
+
+    fn list_skills(&self, arguments: &JsonValue) -> Result<JsonValue, ToolError> {
+        let skills = self.scan_filtered(arguments)?;
+        Ok(json!({ "skills": skills.iter().map(SkillRecord::new).collect::<Vec<_>>() }))
+    }
+
+    fn get_skill(&self, arguments: &JsonValue) -> Result<JsonValue, ToolError> {
+        let skills = self.scan_filtered(arguments)?;
+        let matched_skills = match (string_argument(arguments, "id"), string_argument(arguments, "name")) {
+            (Some(id), _) => delete::dedupe_by_path(skills.iter().filter(|skill| skill.id() == id)),
+            (None, Some(name)) => delete::dedupe_by_path(skills.iter().filter(|skill| {
+                skill.name.eq_ignore_ascii_case(name) || skill.qualified_name().eq_ignore_ascii_case(name)
+            })),
+            (None, None) => return Err(ToolError("需要 id 或 name 参数".to_string())),
+        };
+        let skill = match matched_skills.as_slice() {
+            [] => return Err(ToolError("未找到匹配的 skill".to_string())),
+            [skill] => *skill,
+            _ => {
+                let candidates: Vec<String> = matched_skills
+                    .iter()
+                    .map(|skill| format!("{} ({} > {})", skill.id(), skill.tool, skill.qualified_name()))
+                    .collect();
+                return Err(ToolError(format!(
+                    "有多个同名 skill，请使用 id 或 tool 参数指定: {}",
+                    candidates.join(", ")
+                )));
+            }
+        };
+
+        let main_file_path = skill.main_file_path();
+        let content = std::fs::read_to_string(&main_file_path)
+            .map_err(|e| ToolError(format!("无法读取 {}: {}", main_file_path.display(), e)))?;
+        let mut skill_document = serde_json::to_value(SkillRecord::new(skill)).unwrap_or_default();
+        skill_document["content"] = JsonValue::String(content);
+        skill_document["files"] = json!(skill.list_files());
+        Ok(skill_document)
+    }
+
+    fn search_skills(&self, arguments: &JsonValue) -> Result<JsonValue, ToolError> {
+        let query = string_argument(arguments, "query")
+            .ok_or_else(|| ToolError("需要 query 参数".to_string()))?
+            .to_lowercase();
+        let limit = arguments
+            .get("limit")
+            .and_then(JsonValue::as_u64)
+            .map_or(DEFAULT_SEARCH_LIMIT, |limit| limit as usize);
+        let skills = self.scan_filtered(arguments)?;
+        let matched_skills: Vec<SkillRecord> = skills
+            .iter()
+            .filter(|skill| {
+                [
+                    Some(skill.qualified_name()),
+                    skill.description.clone(),
+                    Some(skill.tool.clone()),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|text| text.to_lowercase().contains(&query))
+            })
+            .take(limit)
+            .map(SkillRecord::new)
+            .collect();
+        Ok(json!({ "skills": matched_skills }))
+    }
+}
+
+fn string_argument<'a>(arguments: &'a JsonValue, key: &str) -> Option<&'a str> {
+    arguments.get(key).and_then(JsonValue::as_str)
+}
+
+fn initialize_result(params: &JsonValue) -> JsonValue {
+    let requested_version = params.get("protocolVersion").and_then(JsonValue::as_str);
+    let protocol_version = requested_version
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(MCP_PROTOCOL_VERSION);
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "列出并读取本机各 AI 工具（Claude Code、Cursor、Codex 等）中安装的 skills 与规则文件。",
+    })
+}
+
+fn tool_definitions() -> JsonValue {
+    let filter_properties = json!({
+        "tool": { "type": "string", "description": "只包含该工具：工具名中的单词（如 codex）或 glob" },
+        "kind": { "type": "string", "description": "只包含该类别，如 skill、cursor-rule、claude-command" },
+    });
+    let with_filters = |properties: JsonValue| {
+        let mut merged_properties = filter_properties.clone();
+        if let (Some(merged), JsonValue::Object(extra)) = (merged_properties.as_object_mut(), properties) {
+            merged.extend(extra);
+        }
+        merged_properties
+    };
+    json!([
+        {
+            "name": "list_skills",
+            "description": "列出各工具中安装的 skills（含规则与指令文件），返回 id、名称、类别、工具、路径与描述",
+            "inputSchema": { "type": "object", "properties": filter_properties },
+        },
+        {
+            "name": "get_skill",
+            "description": "按 id 或名称获取一个 skill 的完整 SKILL.md（或规则文件）内容与文件列表",
+            "inputSchema": {
+                "type": "object",
+                "properties": with_filters(json!({
+                    "id": { "type": "string", "description": "list_skills 返回的 id" },
+                    "name": { "type": "string", "description": "skill 名称或带分类的名称" },
+                })),
+            },
+        },
+        {
+            "name": "search_skills",
+            "description": "按关键字（不区分大小写）搜索 skill 的名称、分类、描述与工具名",
+            "inputSchema": {
+                "type": "object",
+                "properties": with_filters(json!({
+                    "query": { "type": "string" },
+                    "limit": { "type": "integer", "minimum": 1, "default": DEFAULT_SEARCH_LIMIT },
+                })),
+                "required": ["query"],
+            },
+        },
+    ])
+}
+
+fn error_response(request_id: JsonValue, error: RpcError) -> JsonValue {
+    json!({
+        "jsonrpc": "2.0",
+        "id": request_id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
@@ -0,0 +1,848 @@
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+use std::time::{Duration, Instant};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use skills_scanner::artifact::ArtifactKind;
+use skills_scanner::delete::{DeleteOutcome, DeleteStatus};
+use skills_scanner::dupes::DupeReport;
+use skills_scanner::finding::{Finding, Rule, Severity};
+use skills_scanner::install::InstallOutcome;
+use skills_scanner::link::{RepairOutcome, RepairStatus, UnlinkOutcome, UnlinkStatus};
+use skills_scanner::mcp::McpConfigFile;
+use skills_scanner::registry::{self, ToolEntry, ToolRegistry};
+use skills_scanner::resolve::{ResolvedSkill, ToolResolution};
+use skills_scanner::sarif;
+use skills_scanner::scanner::{self, RootKind, ScanRoot, Scanner};
+use skills_scanner::skill::{Skill, SkillRecord};
+use skills_scanner::sync::SyncOutcome;
+use skills_scanner::trash::TrashEntry;
+use skills_scanner::watch::WatchEvent;
+use crate::ui;
+
+/// JSON 文档的 schema 版本，字段发生不兼容变更时递增
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+const GENERATOR_NAME: &str = env!("CARGO_PKG_NAME");
+const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// 带样式的文本（默认）
+    Text,
+    /// 单个 JSON 文档
+    Json,
+    /// 每行一个 JSON 对象，边扫描边输出
+    Ndjson,
+    /// SARIF 2.1.0 日志，仅适用于 lint/audit 等问题报告
+    Sarif,
+}
+
+impl OutputFormat {
+    /// 是否为机器可读格式（此时不应向 stdout 输出提示信息）
+    pub fn is_machine_readable(self) -> bool {
+        !matches!(self, OutputFormat::Text)
+    }
+
+    /// 只有 lint/audit 等问题报告支持 sarif，其余子命令用它在执行前拒绝
+    pub fn ensure_not_sarif(self) -> io::Result<()> {
+        if self == OutputFormat::Sarif {
+            return Err(sarif_unsupported_error());
+        }
+        Ok(())
+    }
+}
+
+fn sarif_unsupported_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "sarif 格式仅适用于 lint/audit 子命令")
+}
+
+/// 单个扫描根目录的 JSON 表示
+#[derive(Serialize)]
+struct RootRecord<'a> {
+    tool: &'a str,
+    path: String,
+    root_kind: RootKind,
+    artifact_kind: ArtifactKind,
+}
+
+impl<'a> RootRecord<'a> {
+    fn new(scan_root: &'a ScanRoot) -> Self {
+        Self {
+            tool: &scan_root.tool_name,
+            path: scan_root.path.display().to_string(),
+            root_kind: scan_root.kind,
+            artifact_kind: scan_root.artifact_kind,
+        }
+    }
+}
+
+/// `--list --format json` 输出的完整文档
+#[derive(Serialize)]
+struct SkillListDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    roots: Vec<RootRecord<'a>>,
+    skill_count: usize,
+    skills: Vec<SkillRecord<'a>>,
+}
+
+/// NDJSON 流中的一条记录
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum StreamRecord<'a> {
+    /// 发现的一个 skill
+    Skill(SkillRecord<'a>),
+    /// 扫描结束后的汇总
+    Summary {
+        schema_version: u32,
+        generator: &'static str,
+        generator_version: &'static str,
+        root_count: usize,
+        skill_count: usize,
+        elapsed_ms: u128,
+    },
+}
+
+/// 逐条写出 NDJSON 记录的写入器
+pub struct NdjsonWriter<W: Write> {
+    writer: W,
+    skill_count: usize,
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            skill_count: 0,
+        }
+    }
+
+    /// 写出一个 skill 记录并立即 flush，便于下游管道实时消费
+    pub fn write_skill(&mut self, skill: &Skill) -> io::Result<()> {
+        self.write_record(&StreamRecord::Skill(SkillRecord::new(skill)))?;
+        self.skill_count += 1;
+        Ok(())
+    }
+
+    /// 写出汇总记录，结束整个流
+    pub fn finish(mut self, root_count: usize, elapsed_ms: u128) -> io::Result<()> {
+        let summary = StreamRecord::Summary {
+            schema_version: JSON_SCHEMA_VERSION,
+            generator: GENERATOR_NAME,
+            generator_version: GENERATOR_VERSION,
+            root_count,
+            skill_count: self.skill_count,
+            elapsed_ms,
+        };
+        self.write_record(&summary)
+    }
+
+    fn write_record(&mut self, record: &StreamRecord) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        writeln!(self.writer)?;
+        self.writer.flush()
+    }
+}
+
+/// 按指定格式输出 skills 列表；`scan_elapsed` 为扫描耗时，写入 NDJSON 汇总
+pub fn print_skills(
+    format: OutputFormat,
+    roots: &[ScanRoot],
+    skills: &[Skill],
+    scan_elapsed: Duration,
+) -> io::Result<()> {
+    match format {
+        OutputFormat::Sarif => Err(sarif_unsupported_error()),
+        OutputFormat::Text => {
+            ui::display_skills(skills);
+            Ok(())
+        }
+        OutputFormat::Json => {
+            let stdout = io::stdout();
+            let mut writer = stdout.lock();
+            write_skills_json(&mut writer, roots, skills)?;
+            writer.flush()
+        }
+        OutputFormat::Ndjson => {
+            let mut stream_writer = NdjsonWriter::new(io::stdout().lock());
+            for skill in skills {
+                stream_writer.write_skill(skill)?;
+            }
+            stream_writer.finish(roots.len(), scan_elapsed.as_millis())
+        }
+    }
+}
+
+/// 边扫描边以 NDJSON 输出，每个 skill 一行，最后写出汇总
+///
+/// 行的顺序与非流式输出相同：排在最前面、尚未完成的扫描根目录中的 skill 读取到就写出，
+/// 后面的根目录要等前面的都完成；某个根目录很慢时，它之后的行都会被推迟。
+pub fn stream_skills_ndjson(scanner: &Scanner) -> io::Result<()> {
+    let started_at = Instant::now();
+    let mut stream_writer = NdjsonWriter::new(io::stdout().lock());
+    let mut write_result = Ok(());
+
+    // 写入失败（如下游 `head` 已关闭管道）后立即停止扫描
+    scanner.scan_each(|skill| {
+        write_result = stream_writer.write_skill(&skill);
+        if write_result.is_ok() {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    });
+    write_result?;
+
+    stream_writer.finish(scanner.path_count(), started_at.elapsed().as_millis())
+}
+
+/// `lint` 等命令输出的问题列表文档
+#[derive(Serialize)]
+struct FindingListDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    error_count: usize,
+    warning_count: usize,
+    note_count: usize,
+    findings: &'a [Finding],
+}
+
+/// 按指定格式输出问题列表；`rules` 为产生这些问题的规则集，用于 SARIF 规则元数据
+pub fn print_findings(format: OutputFormat, rules: &[Rule], findings: &[Finding]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_findings(findings);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let count_severity = |severity: Severity| {
+                findings.iter().filter(|finding| finding.severity == severity).count()
+            };
+            let document = FindingListDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                error_count: count_severity(Severity::Error),
+                warning_count: count_severity(Severity::Warning),
+                note_count: count_severity(Severity::Note),
+                findings,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Sarif => {
+            let workspace_root = scanner::workspace_root();
+            sarif::write_sarif(&mut writer, rules, findings, workspace_root.as_deref())?;
+        }
+        OutputFormat::Ndjson => {
+            for finding in findings {
+                serde_json::to_writer(&mut writer, finding)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+    writer.flush()
+}
+
+/// `tools` 子命令输出的注册表文档
+#[derive(Serialize)]
+struct ToolListDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    user_registry_path: Option<String>,
+    tools: &'a [ToolEntry],
+}
+
+/// 按指定格式输出工具注册表
+pub fn print_tools(format: OutputFormat, registry: &ToolRegistry) -> io::Result<()> {
+    let user_registry_path = registry::user_registry_path();
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_tools(registry.entries(), user_registry_path.as_deref());
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = ToolListDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                user_registry_path: user_registry_path.map(|path| path.display().to_string()),
+                tools: registry.entries(),
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for tool_entry in registry.entries() {
+                serde_json::to_writer(&mut writer, tool_entry)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `delete` 子命令输出的结果文档
+#[derive(Serialize)]
+struct DeleteResultDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    dry_run: bool,
+    permanent: bool,
+    matched_count: usize,
+    failed_count: usize,
+    results: &'a [DeleteOutcome],
+}
+
+/// 按指定格式输出删除结果
+pub fn print_delete_outcomes(
+    format: OutputFormat,
+    delete_outcomes: &[DeleteOutcome],
+    dry_run: bool,
+    permanent: bool,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_delete_outcomes(delete_outcomes);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = DeleteResultDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                dry_run,
+                permanent,
+                matched_count: delete_outcomes.len(),
+                failed_count: delete_outcomes
+                    .iter()
+                    .filter(|delete_outcome| delete_outcome.status == DeleteStatus::Failed)
+                    .count(),
+                results: delete_outcomes,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for delete_outcome in delete_outcomes {
+                serde_json::to_writer(&mut writer, delete_outcome)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `install` 子命令输出的结果文档
+#[derive(Serialize)]
+struct InstallResultDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    source: SkillRecord<'a>,
+    results: &'a [InstallOutcome],
+}
+
+/// 按指定格式输出安装结果
+pub fn print_install_outcomes(
+    format: OutputFormat,
+    source_skill: &Skill,
+    install_outcomes: &[InstallOutcome],
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_install_outcomes(source_skill, install_outcomes);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = InstallResultDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                source: SkillRecord::new(source_skill),
+                results: install_outcomes,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for install_outcome in install_outcomes {
+                serde_json::to_writer(&mut writer, install_outcome)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `resolve` 子命令输出的文档
+#[derive(Serialize)]
+struct ResolutionDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    tools: &'a [ToolResolution],
+}
+
+/// NDJSON 流中的一条解析记录，附带所属工具族
+#[derive(Serialize)]
+struct ResolvedSkillRecord<'a> {
+    tool_family: &'a str,
+    #[serde(flatten)]
+    skill: &'a ResolvedSkill,
+}
+
+/// 按指定格式输出各工具的生效 skills
+pub fn print_resolution(format: OutputFormat, tool_resolutions: &[ToolResolution]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_resolution(tool_resolutions);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = ResolutionDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                tools: tool_resolutions,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for tool_resolution in tool_resolutions {
+                for resolved_skill in &tool_resolution.skills {
+                    let record = ResolvedSkillRecord {
+                        tool_family: &tool_resolution.tool,
+                        skill: resolved_skill,
+                    };
+                    serde_json::to_writer(&mut writer, &record)?;
+                    writeln!(writer)?;
+                }
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `sync` 子命令输出的结果文档
+#[derive(Serialize)]
+struct SyncResultDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    dry_run: bool,
+    source: SkillRecord<'a>,
+    results: &'a [SyncOutcome],
+}
+
+/// 按指定格式输出同步计划或结果
+pub fn print_sync_outcomes(
+    format: OutputFormat,
+    source_skill: &Skill,
+    sync_outcomes: &[SyncOutcome],
+    dry_run: bool,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_sync_outcomes(sync_outcomes);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = SyncResultDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                dry_run,
+                source: SkillRecord::new(source_skill),
+                results: sync_outcomes,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for sync_outcome in sync_outcomes {
+                serde_json::to_writer(&mut writer, sync_outcome)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `repair-links` 子命令输出的结果文档
+#[derive(Serialize)]
+struct RepairResultDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    broken_count: usize,
+    unresolved_count: usize,
+    results: &'a [RepairOutcome],
+}
+
+/// 按指定格式输出失效链接的修复结果
+pub fn print_repair_outcomes(format: OutputFormat, repair_outcomes: &[RepairOutcome]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_repair_outcomes(repair_outcomes);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = RepairResultDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                broken_count: repair_outcomes.len(),
+                unresolved_count: repair_outcomes
+                    .iter()
+                    .filter(|repair_outcome| repair_outcome.status == RepairStatus::Unresolved)
+                    .count(),
+                results: repair_outcomes,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for repair_outcome in repair_outcomes {
+                serde_json::to_writer(&mut writer, repair_outcome)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `unlink` 子命令输出的结果文档
+#[derive(Serialize)]
+struct UnlinkResultDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    link_count: usize,
+    failed_count: usize,
+    results: &'a [UnlinkOutcome],
+}
+
+/// 按指定格式输出断开链接的结果
+pub fn print_unlink_outcomes(format: OutputFormat, unlink_outcomes: &[UnlinkOutcome]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_unlink_outcomes(unlink_outcomes);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = UnlinkResultDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                link_count: unlink_outcomes.len(),
+                failed_count: unlink_outcomes
+                    .iter()
+                    .filter(|unlink_outcome| unlink_outcome.status == UnlinkStatus::Failed)
+                    .count(),
+                results: unlink_outcomes,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for unlink_outcome in unlink_outcomes {
+                serde_json::to_writer(&mut writer, unlink_outcome)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `dupes` 子命令输出的报告文档
+#[derive(Serialize)]
+struct DupeReportDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    #[serde(flatten)]
+    report: &'a DupeReport,
+}
+
+/// NDJSON 流中的一组重复或分叉副本
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+enum DupeRecord<'a, T> {
+    Identical(&'a T),
+    Diverged(&'a T),
+    Renamed(&'a T),
+}
+
+/// 按指定格式输出重复与分叉的 skills
+pub fn print_dupes(format: OutputFormat, dupe_report: &DupeReport) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_dupes(dupe_report);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = DupeReportDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                report: dupe_report,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for identical_group in &dupe_report.identical {
+                serde_json::to_writer(&mut writer, &DupeRecord::Identical(identical_group))?;
+                writeln!(writer)?;
+            }
+            for diverged_group in &dupe_report.diverged {
+                serde_json::to_writer(&mut writer, &DupeRecord::Diverged(diverged_group))?;
+                writeln!(writer)?;
+            }
+            for renamed_group in &dupe_report.renamed {
+                serde_json::to_writer(&mut writer, &DupeRecord::Renamed(renamed_group))?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `trash list` 输出的回收站文档
+#[derive(Serialize)]
+struct TrashListDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    entries: &'a [TrashEntry],
+}
+
+/// 按指定格式输出回收站条目
+pub fn print_trash_entries(format: OutputFormat, trash_entries: &[TrashEntry]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_trash_entries(trash_entries);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = TrashListDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                entries: trash_entries,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for trash_entry in trash_entries {
+                serde_json::to_writer(&mut writer, trash_entry)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// `mcp` 输出的配置文档
+#[derive(Serialize)]
+struct McpInventoryDocument<'a> {
+    schema_version: u32,
+    generator: &'static str,
+    generator_version: &'static str,
+    config_count: usize,
+    server_count: usize,
+    configs: &'a [McpConfigFile],
+}
+
+/// 按指定格式输出 MCP 配置文件；NDJSON 每个文件一行
+pub fn print_mcp_config_files(format: OutputFormat, config_files: &[McpConfigFile]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            ui::display_mcp_config_files(config_files);
+            return Ok(());
+        }
+        OutputFormat::Json => {
+            let document = McpInventoryDocument {
+                schema_version: JSON_SCHEMA_VERSION,
+                generator: GENERATOR_NAME,
+                generator_version: GENERATOR_VERSION,
+                config_count: config_files.len(),
+                server_count: config_files.iter().map(|config_file| config_file.servers.len()).sum(),
+                configs: config_files,
+            };
+            serde_json::to_writer_pretty(&mut writer, &document)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for config_file in config_files {
+                serde_json::to_writer(&mut writer, config_file)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Sarif => return Err(sarif_unsupported_error()),
+    }
+    writer.flush()
+}
+
+/// 输出一批监视事件；NDJSON 每个事件一行并立即 flush，JSON 文档无法表示持续的事件流
+pub fn print_watch_events(format: OutputFormat, watch_events: &[WatchEvent]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut writer = stdout.lock();
+    match format {
+        OutputFormat::Text => {
+            watch_events.iter().for_each(ui::display_watch_event);
+            return Ok(());
+        }
+        OutputFormat::Ndjson => {
+            for watch_event in watch_events {
+                serde_json::to_writer(&mut writer, watch_event)?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Json | OutputFormat::Sarif => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "watch 仅支持 text 与 ndjson 格式",
+            ));
+        }
+    }
+    writer.flush()
+}
+
+/// 将 skills 列表写为 JSON 文档
+fn write_skills_json<W: Write>(writer: &mut W, roots: &[ScanRoot], skills: &[Skill]) -> io::Result<()> {
+    let document = SkillListDocument {
+        schema_version: JSON_SCHEMA_VERSION,
+        generator: GENERATOR_NAME,
+        generator_version: GENERATOR_VERSION,
+        roots: roots.iter().map(RootRecord::new).collect(),
+        skill_count: skills.len(),
+        skills: skills.iter().map(SkillRecord::new).collect(),
+    };
+    serde_json::to_writer_pretty(&mut *writer, &document)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn skills_json_document_contains_schema_version_and_sources() {
+        let scan_root = ScanRoot {
+            tool_name: "Claude Code".to_string(),
+            path: PathBuf::from("/home/user/.claude/skills"),
+            kind: RootKind::UserHome,
+            max_depth: None,
+            artifact_kind: ArtifactKind::Skill,
+        };
+        let skill = Skill {
+            name: "dom-cleanup".to_string(),
+            tool: "Claude Code".to_string(),
+            kind: ArtifactKind::Skill,
+            category: None,
+            path: PathBuf::from("/home/user/.claude/skills/dom-cleanup"),
+            description: Some("Simplify DOM".to_string()),
+            manifest: None,
+            root: scan_root.path.clone(),
+            root_kind: scan_root.kind,
+            link_target: None,
+            rule: None,
+            extension: None,
+            content_hash: None,
+        };
+
+        let mut buffer = Vec::new();
+        write_skills_json(&mut buffer, &[scan_root], &[skill]).expect("failed to write json");
+        let document: serde_json::Value =
+            serde_json::from_slice(&buffer).expect("output is not valid json");
+
+        assert_eq!(document["schema_version"], JSON_SCHEMA_VERSION);
+        assert_eq!(document["skill_count"], 1);
+        assert_eq!(document["skills"][0]["name"], "dom-cleanup");
+        assert_eq!(document["skills"][0]["root_kind"], "user-home");
+        assert_eq!(document["skills"][0]["root"], "/home/user/.claude/skills");
+        assert_eq!(document["roots"][0]["tool"], "Claude Code");
+    }
+
+    #[test]
+    fn ndjson_writer_emits_one_record_per_line_and_summary() {
+        let skill = Skill {
+            name: "dom-cleanup".to_string(),
+            tool: "Cursor".to_string(),
+            kind: ArtifactKind::Skill,
+            category: None,
+            path: PathBuf::from("/repo/.cursor/skills/dom-cleanup"),
+            description: None,
+            manifest: None,
+            root: PathBuf::from("/repo/.cursor/skills"),
+            root_kind: RootKind::Workspace,
+            link_target: None,
+            rule: None,
+            extension: None,
+            content_hash: None,
+        };
+
+        let mut buffer = Vec::new();
+        let mut stream_writer = NdjsonWriter::new(&mut buffer);
+        stream_writer.write_skill(&skill).expect("failed to write skill record");
+        stream_writer.write_skill(&skill).expect("failed to write skill record");
+        stream_writer.finish(1, 5).expect("failed to write summary record");
+
+        let records: Vec<serde_json::Value> = String::from_utf8(buffer)
+            .expect("output is not utf-8")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("line is not valid json"))
+            .collect();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["type"], "skill");
+        assert_eq!(records[0]["root_kind"], "workspace");
+        assert_eq!(records[2]["type"], "summary");
+        assert_eq!(records[2]["skill_count"], 2);
+        assert_eq!(records[2]["root_count"], 1);
+    }
+}
@@ -0,0 +1,868 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::artifact::ArtifactKind;
+use crate::scanner::RootKind;
+
+/// 用户工具注册表文件名
+const USER_REGISTRY_FILE_NAME: &str = "tools.toml";
+/// 本工具在配置目录下使用的子目录名
+const APP_CONFIG_DIRECTORY_NAME: &str = "skills-scanner";
+
+/// 相对路径目录配置
+#[derive(Debug, Clone, Copy)]
+struct RelativeSkillPathConfig {
+    /// 工具名称
+    tool_name: &'static str,
+    /// 相对路径片段
+    path_parts: &'static [&'static str],
+}
+
+/// 用户目录下的主流技能路径（全平台：Windows/Linux/macOS）
+const USER_HOME_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
+    RelativeSkillPathConfig {
+        tool_name: "Claude Code",
+        path_parts: &[".claude", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "OpenAI Codex",
+        path_parts: &[".agents", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "OpenAI Codex (Legacy)",
+        path_parts: &[".codex", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Gemini CLI",
+        path_parts: &[".gemini", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Windsurf",
+        path_parts: &[".codeium", "windsurf", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "GitHub Copilot",
+        path_parts: &[".copilot", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Cursor",
+        path_parts: &[".cursor", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Cline",
+        path_parts: &[".cline", "skills"],
+    },
+    // 兼容历史目录
+    RelativeSkillPathConfig {
+        tool_name: "Gemini Antigravity (Legacy)",
+        path_parts: &[".gemini", "antigravity", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Windsurf (Legacy)",
+        path_parts: &[".windsurf", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Codeium (Legacy)",
+        path_parts: &[".codeium", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Continue (Legacy)",
+        path_parts: &[".continue", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Roo Code (Legacy)",
+        path_parts: &[".roo-code", "skills"],
+    },
+];
+
+/// 配置目录下的主流技能路径（遵循平台标准配置路径）
+const USER_CONFIG_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
+    RelativeSkillPathConfig {
+        tool_name: "OpenCode",
+        path_parts: &["opencode", "skills"],
+    },
+];
+
+/// 工作区目录下的技能路径（从当前目录向上查找到 git 根）
+const WORKSPACE_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
+    // 官方路径
+    RelativeSkillPathConfig {
+        tool_name: "Claude Code (Project)",
+        path_parts: &[".claude", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "OpenAI Codex (Project)",
+        path_parts: &[".agents", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "GitHub Copilot (Project)",
+        path_parts: &[".github", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Gemini CLI (Project)",
+        path_parts: &[".gemini", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Windsurf (Project)",
+        path_parts: &[".windsurf", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Cursor (Project)",
+        path_parts: &[".cursor", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Cline (Project)",
+        path_parts: &[".cline", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Cline Compatibility (Project)",
+        path_parts: &[".clinerules", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "OpenCode (Project)",
+        path_parts: &[".opencode", "skills"],
+    },
+    // 扩展目录（Agent Skills 生态常见目录）
+    RelativeSkillPathConfig {
+        tool_name: "Antigravity (Project)",
+        path_parts: &[".agent", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Augment (Project)",
+        path_parts: &[".augment", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Codebuddy (Project)",
+        path_parts: &[".codebuddy", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "CommandCode (Project)",
+        path_parts: &[".commandcode", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Continue (Project)",
+        path_parts: &[".continue", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Crush (Project)",
+        path_parts: &[".crush", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Factory (Project)",
+        path_parts: &[".factory", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Goose (Project)",
+        path_parts: &[".goose", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "iFlow (Project)",
+        path_parts: &[".iflow", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Junie (Project)",
+        path_parts: &[".junie", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "KiloCode (Project)",
+        path_parts: &[".kilocode", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Kiro (Project)",
+        path_parts: &[".kiro", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Kode (Project)",
+        path_parts: &[".kode", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "MCP Jam (Project)",
+        path_parts: &[".mcpjam", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Mux (Project)",
+        path_parts: &[".mux", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Neovate (Project)",
+        path_parts: &[".neovate", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "OpenHands (Project)",
+        path_parts: &[".openhands", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Pi (Project)",
+        path_parts: &[".pi", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Pochi (Project)",
+        path_parts: &[".pochi", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Qoder (Project)",
+        path_parts: &[".qoder", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Qwen (Project)",
+        path_parts: &[".qwen", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Roo (Project)",
+        path_parts: &[".roo", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Trae (Project)",
+        path_parts: &[".trae", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Vibe (Project)",
+        path_parts: &[".vibe", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Zencoder (Project)",
+        path_parts: &[".zencoder", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Adal (Project)",
+        path_parts: &[".adal", "skills"],
+    },
+    // 兼容历史目录
+    RelativeSkillPathConfig {
+        tool_name: "OpenAI Codex (Legacy Project)",
+        path_parts: &[".codex", "skills"],
+    },
+    RelativeSkillPathConfig {
+        tool_name: "Roo Code (Legacy Project)",
+        path_parts: &[".roo-code", "skills"],
+    },
+];
+
+/// 规则与指令文件的路径配置
+#[derive(Debug, Clone, Copy)]
+struct RelativeArtifactPathConfig {
+    /// 工具名称
+    tool_name: &'static str,
+    /// 基准位置
+    root_base: RootBase,
+    /// 相对路径片段（指向单个文件或包含规则文件的目录）
+    path_parts: &'static [&'static str],
+    /// 类别
+    kind: ArtifactKind,
+    /// 查找深度；None 时使用全局设置
+    max_depth: Option<usize>,
+}
+
+/// Claude Code 命令可放在子目录中作为命名空间（如 `frontend/component.md`）
+const CLAUDE_COMMAND_MAX_DEPTH: usize = 3;
+
+/// 各工具 SKILL.md 之外的规则与指令文件
+const ARTIFACT_PATH_CONFIGS: &[RelativeArtifactPathConfig] = &[
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Commands",
+        root_base: RootBase::Home,
+        path_parts: &[".claude", "commands"],
+        kind: ArtifactKind::ClaudeCommand,
+        max_depth: Some(CLAUDE_COMMAND_MAX_DEPTH),
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Agents",
+        root_base: RootBase::Home,
+        path_parts: &[".claude", "agents"],
+        kind: ArtifactKind::ClaudeAgent,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Memory",
+        root_base: RootBase::Home,
+        path_parts: &[".claude", "CLAUDE.md"],
+        kind: ArtifactKind::ClaudeMd,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "OpenAI Codex AGENTS.md",
+        root_base: RootBase::Home,
+        path_parts: &[".codex", "AGENTS.md"],
+        kind: ArtifactKind::AgentsMd,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Commands (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &[".claude", "commands"],
+        kind: ArtifactKind::ClaudeCommand,
+        max_depth: Some(CLAUDE_COMMAND_MAX_DEPTH),
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Agents (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &[".claude", "agents"],
+        kind: ArtifactKind::ClaudeAgent,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Claude Code Memory (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &["CLAUDE.md"],
+        kind: ArtifactKind::ClaudeMd,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "AGENTS.md (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &["AGENTS.md"],
+        kind: ArtifactKind::AgentsMd,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Cursor Rules (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &[".cursor", "rules"],
+        kind: ArtifactKind::CursorRule,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "GitHub Copilot Instructions (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &[".github", "copilot-instructions.md"],
+        kind: ArtifactKind::CopilotInstructions,
+        max_depth: None,
+    },
+    RelativeArtifactPathConfig {
+        tool_name: "Windsurf Rules (Project)",
+        root_base: RootBase::Workspace,
+        path_parts: &[".windsurfrules"],
+        kind: ArtifactKind::WindsurfRules,
+        max_depth: None,
+    },
+];
+
+#[cfg(not(windows))]
+const UNIX_SYSTEM_SKILL_PATHS: &[(&str, &str)] = &[("OpenAI Codex (Admin)", "/etc/codex/skills")];
+
+/// 工具 skills 目录的基准位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RootBase {
+    /// 用户目录 (`~`)
+    Home,
+    /// 平台配置目录
+    Config,
+    /// 工作区（当前目录到 git 根的每一级）
+    Workspace,
+    /// 绝对路径
+    Absolute,
+}
+
+impl RootBase {
+    /// 与 tools.toml 中写法一致的标签
+    pub fn label(self) -> &'static str {
+        match self {
+            RootBase::Home => "home",
+            RootBase::Config => "config",
+            RootBase::Workspace => "workspace",
+            RootBase::Absolute => "absolute",
+        }
+    }
+
+    /// 对应的扫描根目录类别
+    pub fn root_kind(self) -> RootKind {
+        match self {
+            RootBase::Home => RootKind::UserHome,
+            RootBase::Config => RootKind::UserConfig,
+            RootBase::Workspace => RootKind::Workspace,
+            RootBase::Absolute => RootKind::System,
+        }
+    }
+}
+
+/// 注册表条目的来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EntrySource {
+    /// 内置默认值
+    Builtin,
+    /// 来自用户 tools.toml（新增或覆盖）
+    User,
+}
+
+/// 一个工具的 skills 目录定义
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolEntry {
+    /// 工具名称，同时是合并时的唯一键
+    pub name: String,
+    /// 基准位置
+    pub root: RootBase,
+    /// 相对于基准位置的路径（`absolute` 时为绝对路径），支持 `~` 与环境变量
+    pub path: String,
+    /// 是否启用
+    pub enabled: bool,
+    /// 查找 skill 目录的最大深度（用于按分类目录组织的布局）；为 None 时使用全局设置
+    pub max_depth: Option<usize>,
+    /// 该路径下查找的类别，默认为 skill
+    pub kind: ArtifactKind,
+    /// 条目来源
+    pub source: EntrySource,
+}
+
+impl ToolEntry {
+    fn builtin(name: &str, root: RootBase, path: String) -> Self {
+        Self {
+            name: name.to_string(),
+            root,
+            path,
+            enabled: true,
+            max_depth: None,
+            kind: ArtifactKind::Skill,
+            source: EntrySource::Builtin,
+        }
+    }
+
+    /// 将路径解析到指定基准目录上；存在未定义的环境变量时返回指明该条目的错误
+    pub fn resolve(&self, base_directory_path: &Path) -> Result<PathBuf, RegistryError> {
+        let expanded_path = expand_path_variables(&self.path).map_err(|undefined_variable| {
+            RegistryError::UndefinedVariable {
+                name: self.name.clone(),
+                path: self.path.clone(),
+                variable: undefined_variable.0,
+            }
+        })?;
+        let expanded_path = PathBuf::from(expanded_path);
+        if expanded_path.is_absolute() {
+            return Ok(expanded_path);
+        }
+        Ok(base_directory_path.join(expanded_path))
+    }
+}
+
+/// 用户 tools.toml 中的一条记录；覆盖已有条目时可以只写 `enabled`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserToolEntry {
+    name: String,
+    root: Option<RootBase>,
+    path: Option<String>,
+    enabled: Option<bool>,
+    max_depth: Option<usize>,
+    kind: Option<ArtifactKind>,
+}
+
+/// 用户 tools.toml 的文件结构
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct UserRegistryFile {
+    #[serde(default)]
+    tools: Vec<UserToolEntry>,
+}
+
+/// 加载工具注册表失败的原因
+#[derive(Debug)]
+pub enum RegistryError {
+    /// 读取文件失败
+    Io { path: PathBuf, source: io::Error },
+    /// TOML 解析失败
+    Parse { path: PathBuf, source: toml::de::Error },
+    /// 新增条目缺少必要字段
+    IncompleteEntry { path: PathBuf, name: String },
+    /// 条目路径引用了未定义的环境变量，扫描时跳过该条目
+    UndefinedVariable { name: String, path: String, variable: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::Io { path, source } => {
+                write!(f, "无法读取 {}: {}", path.display(), source)
+            }
+            RegistryError::Parse { path, source } => {
+                write!(f, "无法解析 {}: {}", path.display(), source)
+            }
+            RegistryError::IncompleteEntry { path, name } => write!(
+                f,
+                "{} 中新增的工具 `{}` 必须同时指定 root 和 path",
+                path.display(),
+                name
+            ),
+            RegistryError::UndefinedVariable { name, path, variable } => write!(
+                f,
+                "工具 `{}` 的路径 `{}` 引用了未定义的环境变量 `{}`，已跳过该条目",
+                name, path, variable
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+/// 工具注册表：内置默认值与用户配置合并后的结果
+#[derive(Debug, Clone)]
+pub struct ToolRegistry {
+    entries: Vec<ToolEntry>,
+}
+
+impl ToolRegistry {
+    /// 内置默认注册表
+    pub fn builtin() -> Self {
+        let mut entries = Vec::new();
+        let relative_tables = [
+            (RootBase::Home, USER_HOME_SKILL_PATH_CONFIGS),
+            (RootBase::Config, USER_CONFIG_SKILL_PATH_CONFIGS),
+            (RootBase::Workspace, WORKSPACE_SKILL_PATH_CONFIGS),
+        ];
+        for (root_base, path_configs) in relative_tables {
+            for path_config in path_configs {
+                debug_assert!(!path_config.path_parts.is_empty());
+                entries.push(ToolEntry::builtin(
+                    path_config.tool_name,
+                    root_base,
+                    path_config.path_parts.join("/"),
+                ));
+            }
+        }
+
+        for artifact_path_config in ARTIFACT_PATH_CONFIGS {
+            entries.push(ToolEntry {
+                kind: artifact_path_config.kind,
+                max_depth: artifact_path_config.max_depth,
+                ..ToolEntry::builtin(
+                    artifact_path_config.tool_name,
+                    artifact_path_config.root_base,
+                    artifact_path_config.path_parts.join("/"),
+                )
+            });
+        }
+
+        #[cfg(not(windows))]
+        for (tool_name, absolute_path) in UNIX_SYSTEM_SKILL_PATHS {
+            entries.push(ToolEntry::builtin(
+                tool_name,
+                RootBase::Absolute,
+                absolute_path.to_string(),
+            ));
+        }
+
+        Self { entries }
+    }
+
+    /// 加载内置注册表，并合并用户 tools.toml（如果存在）
+    pub fn load() -> Result<Self, RegistryError> {
+        let mut registry = Self::builtin();
+        if let Some(user_registry_path) = user_registry_path() {
+            registry.merge_file(&user_registry_path)?;
+        }
+        Ok(registry)
+    }
+
+    /// 合并一个 tools.toml 文件
+    pub fn merge_file(&mut self, registry_path: &Path) -> Result<(), RegistryError> {
+        let content = fs::read_to_string(registry_path).map_err(|source| RegistryError::Io {
+            path: registry_path.to_path_buf(),
+            source,
+        })?;
+        self.merge_toml(&content, registry_path)
+    }
+
+    /// 合并 tools.toml 内容：同名条目按字段覆盖，新名称追加
+    fn merge_toml(&mut self, content: &str, registry_path: &Path) -> Result<(), RegistryError> {
+        let user_registry: UserRegistryFile =
+            toml::from_str(content).map_err(|source| RegistryError::Parse {
+                path: registry_path.to_path_buf(),
+                source,
+            })?;
+
+        let mut entry_index_by_name: HashMap<String, usize> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (entry.name.clone(), index))
+            .collect();
+
+        for user_entry in user_registry.tools {
+            if let Some(&existing_index) = entry_index_by_name.get(&user_entry.name) {
+                let existing_entry = &mut self.entries[existing_index];
+                if let Some(root) = user_entry.root {
+                    existing_entry.root = root;
+                }
+                if let Some(path) = user_entry.path {
+                    existing_entry.path = path;
+                }
+                if let Some(enabled) = user_entry.enabled {
+                    existing_entry.enabled = enabled;
+                }
+                if let Some(max_depth) = user_entry.max_depth {
+                    existing_entry.max_depth = Some(max_depth);
+                }
+                if let Some(kind) = user_entry.kind {
+                    existing_entry.kind = kind;
+                }
+                existing_entry.source = EntrySource::User;
+                continue;
+            }
+
+            let (root, path) = match (user_entry.root, user_entry.path) {
+                (Some(root), Some(path)) => (root, path),
+                _ => {
+                    return Err(RegistryError::IncompleteEntry {
+                        path: registry_path.to_path_buf(),
+                        name: user_entry.name,
+                    })
+                }
+            };
+            entry_index_by_name.insert(user_entry.name.clone(), self.entries.len());
+            self.entries.push(ToolEntry {
+                name: user_entry.name,
+                root,
+                path,
+                enabled: user_entry.enabled.unwrap_or(true),
+                max_depth: user_entry.max_depth,
+                kind: user_entry.kind.unwrap_or_default(),
+                source: EntrySource::User,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// 所有条目（包括已禁用的）
+    pub fn entries(&self) -> &[ToolEntry] {
+        &self.entries
+    }
+
+    /// 路径中存在未定义环境变量的启用条目，扫描时会被跳过
+    pub fn unresolved_entries(&self) -> Vec<RegistryError> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.enabled)
+            .filter_map(|entry| entry.resolve(Path::new("")).err())
+            .collect()
+    }
+
+    /// 指定基准位置下所有启用的条目
+    pub fn enabled_entries(&self, root_base: RootBase) -> impl Iterator<Item = &ToolEntry> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.enabled && entry.root == root_base)
+    }
+}
+
+impl Default for ToolRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// 去掉工具名称末尾的括号后缀，得到工具族名称（如 `Cursor (Project)` → `Cursor`）
+pub fn tool_family(tool_name: &str) -> &str {
+    match tool_name.rfind(" (") {
+        Some(suffix_start) if tool_name.ends_with(')') => &tool_name[..suffix_start],
+        _ => tool_name,
+    }
+}
+
+/// 是否为兼容历史目录的条目（`(Legacy)` 或 `(Legacy Project)`）
+pub fn is_legacy_tool(tool_name: &str) -> bool {
+    tool_name.ends_with(')') && tool_name.contains("(Legacy")
+}
+
+/// 用户 tools.toml 的位置：优先平台配置目录，其次 `~/.config`
+pub fn user_registry_path() -> Option<PathBuf> {
+    app_config_file_path(USER_REGISTRY_FILE_NAME)
+}
+
+/// 在平台配置目录和 `~/.config` 中查找本工具的配置文件，返回第一个存在的路径
+pub fn app_config_file_path(file_name: &str) -> Option<PathBuf> {
+    let mut candidate_paths = Vec::new();
+    if let Some(config_directory_path) = dirs::config_dir() {
+        candidate_paths.push(config_directory_path.join(APP_CONFIG_DIRECTORY_NAME).join(file_name));
+    }
+    if let Some(home_directory_path) = dirs::home_dir() {
+        candidate_paths.push(
+            home_directory_path
+                .join(".config")
+                .join(APP_CONFIG_DIRECTORY_NAME)
+                .join(file_name),
+        );
+    }
+    candidate_paths.into_iter().find(|path| path.is_file())
+}
+
+/// 路径展开时遇到的未定义环境变量名
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndefinedVariable(pub String);
+
+/// 展开路径中的 `~`、`$VAR`、`${VAR}`，Windows 上还支持 `%VAR%`；存在未定义的变量时返回其名称
+///
+/// Unix 上 `%` 是普通的路径字符，不做展开。
+pub fn expand_path_variables(raw_path: &str) -> Result<String, UndefinedVariable> {
+    expand_path_variables_with(raw_path, |name| std::env::var(name).ok())
+}
+
+fn expand_path_variables_with<F>(raw_path: &str, lookup: F) -> Result<String, UndefinedVariable>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let lookup_variable = |name: &str| lookup(name).ok_or_else(|| UndefinedVariable(name.to_string()));
+    let mut expanded_path = String::with_capacity(raw_path.len());
+    let mut rest = raw_path;
+
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        let home_directory_path = dirs::home_dir().ok_or_else(|| UndefinedVariable("~".to_string()))?;
+        expanded_path.push_str(&home_directory_path.to_string_lossy());
+        rest = &rest[1..];
+    }
+
+    while let Some(character) = rest.chars().next() {
+        match character {
+            '$' if rest[1..].starts_with('{') && rest.contains('}') => {
+                let end_index = rest.find('}').expect("closing brace checked above");
+                expanded_path.push_str(&lookup_variable(&rest[2..end_index])?);
+                rest = &rest[end_index + 1..];
+            }
+            '$' => {
+                let name_length = rest[1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len() - 1);
+                if name_length == 0 {
+                    expanded_path.push('$');
+                    rest = &rest[1..];
+                    continue;
+                }
+                expanded_path.push_str(&lookup_variable(&rest[1..=name_length])?);
+                rest = &rest[name_length + 1..];
+            }
+            '%' if cfg!(windows) => match rest[1..].find('%') {
+                Some(name_length) if name_length > 0 => {
+                    expanded_path.push_str(&lookup_variable(&rest[1..=name_length])?);
+                    rest = &rest[name_length + 2..];
+                }
+                _ => {
+                    expanded_path.push('%');
+                    rest = &rest[1..];
+                }
+            },
+            _ => {
+                expanded_path.push(character);
+                rest = &rest[character.len_utf8()..];
+            }
+        }
+    }
+
+    Ok(expanded_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_lookup(name: &str) -> Option<String> {
+        match name {
+            "TOOLS_HOME" => Some("/opt/tools".to_string()),
+            "APPDATA" => Some("C:\\Users\\me\\AppData".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn merge_toml_overrides_disables_and_appends_entries() {
+        let mut registry = ToolRegistry::builtin();
+        let builtin_entry_count = registry.entries().len();
+        let content = r#"
+            [[tools]]
+            name = "Cursor"
+            enabled = false
+
+            [[tools]]
+            name = "Claude Code"
+            path = ".claude/custom-skills"
+
+            [[tools]]
+            name = "My Agent"
+            root = "workspace"
+            path = ".my-agent/skills"
+            max_depth = 3
+        "#;
+
+        registry
+            .merge_toml(content, Path::new("tools.toml"))
+            .expect("failed to merge registry");
+
+        assert_eq!(registry.entries().len(), builtin_entry_count + 1);
+        assert!(registry.enabled_entries(RootBase::Home).all(|entry| entry.name != "Cursor"));
+        let claude_entry = registry
+            .entries()
+            .iter()
+            .find(|entry| entry.name == "Claude Code")
+            .expect("missing Claude Code entry");
+        assert_eq!(claude_entry.path, ".claude/custom-skills");
+        assert_eq!(claude_entry.source, EntrySource::User);
+        assert!(registry
+            .enabled_entries(RootBase::Workspace)
+            .any(|entry| entry.name == "My Agent" && entry.max_depth == Some(3)));
+    }
+
+    #[test]
+    fn merge_toml_rejects_incomplete_new_entries() {
+        let mut registry = ToolRegistry::builtin();
+        let result = registry.merge_toml(
+            "[[tools]]\nname = \"New Tool\"\nroot = \"home\"\n",
+            Path::new("tools.toml"),
+        );
+
+        assert!(matches!(result, Err(RegistryError::IncompleteEntry { .. })));
+    }
+
+    #[test]
+    fn expand_path_variables_supports_unix_and_windows_syntax() {
+        assert_eq!(
+            expand_path_variables_with("$TOOLS_HOME/skills", fake_lookup).as_deref(),
+            Ok("/opt/tools/skills")
+        );
+        assert_eq!(
+            expand_path_variables_with("${TOOLS_HOME}/a", fake_lookup).as_deref(),
+            Ok("/opt/tools/a")
+        );
+        assert_eq!(
+            expand_path_variables_with("$MISSING/skills", fake_lookup),
+            Err(UndefinedVariable("MISSING".to_string()))
+        );
+        assert_eq!(
+            expand_path_variables_with("100%/$", fake_lookup).as_deref(),
+            Ok("100%/$")
+        );
+        if cfg!(windows) {
+            assert_eq!(
+                expand_path_variables_with("%APPDATA%\\skills", fake_lookup).as_deref(),
+                Ok("C:\\Users\\me\\AppData\\skills")
+            );
+        } else {
+            assert_eq!(
+                expand_path_variables_with("/srv/skills%20dir/a%b", fake_lookup).as_deref(),
+                Ok("/srv/skills%20dir/a%b")
+            );
+        }
+    }
+
+    #[test]
+    fn unresolved_entries_name_the_entry_and_variable() {
+        let mut registry = ToolRegistry::builtin();
+        registry
+            .merge_toml(
+                "[[tools]]\nname = \"Team Skills\"\nroot = \"absolute\"\npath = \"${SKILLS_SCANNER_TEST_UNSET}/skills\"\n",
+                Path::new("tools.toml"),
+            )
+            .expect("tools.toml should merge");
+
+        let unresolved_entries = registry.unresolved_entries();
+
+        assert_eq!(unresolved_entries.len(), 1);
+        assert!(matches!(
+            &unresolved_entries[0],
+            RegistryError::UndefinedVariable { name, variable, .. }
+                if name == "Team Skills" && variable == "SKILLS_SCANNER_TEST_UNSET"
+        ));
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::registry;
+use crate::scanner::RootKind;
+use crate::skill::Skill;
+
+/// skill 所在层级，决定同名 skill 之间的优先级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolveScope {
+    /// 项目级目录（当前目录到 git 根）
+    Project,
+    /// 用户级目录
+    User,
+    /// 系统级（管理员）目录，如 `/etc/codex/skills`
+    Admin,
+    /// 通过 `--path` 指定的目录
+    Custom,
+}
+
+impl ResolveScope {
+    fn from_root_kind(root_kind: RootKind) -> Self {
+        match root_kind {
+            RootKind::Workspace => ResolveScope::Project,
+            RootKind::UserHome | RootKind::UserConfig => ResolveScope::User,
+            RootKind::System => ResolveScope::Admin,
+            RootKind::Custom => ResolveScope::Custom,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResolveScope::Project => "项目",
+            ResolveScope::User => "用户",
+            ResolveScope::Admin => "系统",
+            ResolveScope::Custom => "自定义",
+        }
+    }
+}
+
+/// 某个工具视角下的一条 skill
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedSkill {
+    pub name: String,
+    /// 注册表中的工具条目名称
+    pub tool: String,
+    pub path: PathBuf,
+    pub scope: ResolveScope,
+    /// 是否为工具实际加载的副本
+    pub effective: bool,
+    /// 被遮蔽时，生效副本的路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shadowed_by: Option<PathBuf>,
+}
+
+/// 一个工具族的解析结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolResolution {
+    /// 工具族名称（如 `Claude Code` 同时包含用户级与项目级条目）
+    pub tool: String,
+    pub effective_count: usize,
+    pub shadowed_count: usize,
+    /// 按名称排序，同名时生效副本在前
+    pub skills: Vec<ResolvedSkill>,
+}
+
+/// 按工具族应用优先级规则，标出每个工具实际加载的 skill 与被遮蔽的同名 skill
+///
+/// 优先级：项目级 > 用户级 > 系统级；项目级内离当前目录最近的祖先目录优先；
+/// 同一层级内正式目录优先于兼容历史目录（Legacy），其余按扫描顺序。
+pub fn resolve_effective_skills(skills: &[Skill], workspace_search_paths: &[PathBuf]) -> Vec<ToolResolution> {
+    let mut skills_by_family: BTreeMap<&str, BTreeMap<&str, Vec<&Skill>>> = BTreeMap::new();
+    for skill in skills {
+        skills_by_family
+            .entry(registry::tool_family(&skill.tool))
+            .or_default()
+            .entry(&skill.name)
+            .or_default()
+            .push(skill);
+    }
+
+    skills_by_family
+        .into_iter()
+        .map(|(tool_family, skills_by_name)| {
+            let mut resolved_skills = Vec::new();
+            for (_, mut candidates) in skills_by_name {
+                // 稳定排序，优先级相同时保持扫描顺序
+                candidates.sort_by_key(|skill| {
+                    (
+                        ResolveScope::from_root_kind(skill.root_kind),
+                        workspace_distance(&skill.root, workspace_search_paths),
+                        registry::is_legacy_tool(&skill.tool),
+                    )
+                });
+                let effective_path = candidates[0].path.clone();
+                for (candidate_index, skill) in candidates.into_iter().enumerate() {
+                    let effective = candidate_index == 0;
+                    resolved_skills.push(ResolvedSkill {
+                        name: skill.name.clone(),
+                        tool: skill.tool.clone(),
+                        path: skill.path.clone(),
+                        scope: ResolveScope::from_root_kind(skill.root_kind),
+                        effective,
+                        shadowed_by: (!effective).then(|| effective_path.clone()),
+                    });
+                }
+            }
+            let effective_count = resolved_skills.iter().filter(|resolved_skill| resolved_skill.effective).count();
+            ToolResolution {
+                tool: tool_family.to_string(),
+                effective_count,
+                shadowed_count: resolved_skills.len() - effective_count,
+                skills: resolved_skills,
+            }
+        })
+        .collect()
+}
+
+/// 扫描根目录所属的最近工作区祖先目录的下标（0 为当前目录）；不在工作区内时排在最后
+fn workspace_distance(root_path: &Path, workspace_search_paths: &[PathBuf]) -> usize {
+    workspace_search_paths
+        .iter()
+        .position(|workspace_directory_path| root_path.starts_with(workspace_directory_path))
+        .unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifact::ArtifactKind;
+
+    fn skill_at(name: &str, tool: &str, root: &str, root_kind: RootKind) -> Skill {
+        Skill {
+            name: name.to_string(),
+            tool: tool.to_string(),
+            kind: ArtifactKind::Skill,
+            category: None,
+            path: PathBuf::from(root).join(name),
+            description: None,
+            manifest: None,
+            root: PathBuf::from(root),
+            root_kind,
+            link_target: None,
+            rule: None,
+            extension: None,
+            content_hash: None,
+        }
+    }
+
+    #[test]
+    fn nearest_project_copy_shadows_outer_project_user_and_admin_copies() {
+        let workspace_search_paths = vec![PathBuf::from("/repo/packages/web"), PathBuf::from("/repo")];
+        let skills = vec![
+            skill_at("pdf", "OpenAI Codex (Admin)", "/etc/codex/skills", RootKind::System),
+            skill_at("pdf", "OpenAI Codex", "/home/me/.agents/skills", RootKind::UserHome),
+            skill_at("pdf", "OpenAI Codex (Project)", "/repo/.agents/skills", RootKind::Workspace),
+            skill_at("pdf", "OpenAI Codex (Project)", "/repo/packages/web/.agents/skills", RootKind::Workspace),
+            skill_at("docx", "OpenAI Codex (Admin)", "/etc/codex/skills", RootKind::System),
+            skill_at("pdf", "Cursor", "/home/me/.cursor/skills", RootKind::UserHome),
+        ];
+
+        let resolutions = resolve_effective_skills(&skills, &workspace_search_paths);
+        assert_eq!(resolutions.len(), 2);
+
+        let codex_resolution = &resolutions[1];
+        assert_eq!(codex_resolution.tool, "OpenAI Codex");
+        assert_eq!(codex_resolution.effective_count, 2);
+        assert_eq!(codex_resolution.shadowed_count, 3);
+
+        let effective_pdf = codex_resolution
+            .skills
+            .iter()
+            .find(|resolved_skill| resolved_skill.name == "pdf" && resolved_skill.effective)
+            .expect("pdf should resolve");
+        assert_eq!(effective_pdf.path, PathBuf::from("/repo/packages/web/.agents/skills/pdf"));
+        assert!(codex_resolution
+            .skills
+            .iter()
+            .filter(|resolved_skill| resolved_skill.name == "pdf" && !resolved_skill.effective)
+            .all(|resolved_skill| resolved_skill.shadowed_by.as_ref() == Some(&effective_pdf.path)));
+
+        // 其他工具的同名 skill 不受影响
+        assert_eq!(resolutions[0].tool, "Cursor");
+        assert_eq!(resolutions[0].shadowed_count, 0);
+    }
+}
@@ -0,0 +1,317 @@
+use std::io::{self, Write};
+use std::path::{Component, Path};
+use serde::Serialize;
+
+use crate::finding::{Finding, Rule, Severity};
+use crate::fs_util;
+
+const SARIF_SCHEMA_URI: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+/// `originalUriBaseIds` 中表示工作区根目录的键
+const WORKSPACE_URI_BASE_ID: &str = "WORKSPACE";
+const INFORMATION_URI: &str = "https://github.com/qflyb/skills-scanner";
+
+#[derive(Serialize)]
+struct SarifLog<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun<'a> {
+    tool: SarifTool<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_uri_base_ids: Option<serde_json::Value>,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct SarifTool<'a> {
+    driver: SarifDriver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule<'a> {
+    id: &'a str,
+    short_description: SarifText<'a>,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Serialize)]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifText<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    /// 规则不在 `tool.driver.rules` 中时省略，避免指向错误的规则
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: SarifText<'a>,
+    locations: Vec<SarifLocation>,
+    properties: SarifResultProperties<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct SarifResultProperties<'a> {
+    skill: &'a str,
+    tool: &'a str,
+}
+
+/// 将问题列表写为 SARIF 2.1.0 日志
+///
+/// 位于 `workspace_root` 之下的文件使用相对 URI 并引用 `WORKSPACE` 基准目录，其余文件使用绝对 `file://` URI。
+pub fn write_sarif<W: Write>(
+    writer: &mut W,
+    rules: &[Rule],
+    findings: &[Finding],
+    workspace_root: Option<&Path>,
+) -> io::Result<()> {
+    let results = findings
+        .iter()
+        .map(|finding| {
+            let rule_index = rules.iter().position(|rule| rule.id == finding.rule_id);
+            let finding_path = fs_util::absolutize(&finding.path);
+            let artifact_location = match workspace_root
+                .and_then(|root_path| finding_path.strip_prefix(root_path).ok())
+            {
+                Some(relative_path) => SarifArtifactLocation {
+                    uri: relative_path_to_uri(relative_path),
+                    uri_base_id: Some(WORKSPACE_URI_BASE_ID),
+                },
+                None => SarifArtifactLocation {
+                    uri: absolute_path_to_uri(&finding_path),
+                    uri_base_id: None,
+                },
+            };
+            let region = finding.line.map(|start_line| SarifRegion {
+                start_line,
+                start_column: finding.column,
+            });
+
+            SarifResult {
+                rule_id: finding.rule_id,
+                rule_index,
+                level: sarif_level(finding.severity),
+                message: SarifText {
+                    text: &finding.message,
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location,
+                        region,
+                    },
+                }],
+                properties: SarifResultProperties {
+                    skill: &finding.skill,
+                    tool: &finding.tool,
+                },
+            }
+        })
+        .collect();
+
+    let original_uri_base_ids = workspace_root.map(|root_path| {
+        let mut root_uri = absolute_path_to_uri(root_path);
+        if !root_uri.ends_with('/') {
+            root_uri.push('/');
+        }
+        serde_json::json!({ WORKSPACE_URI_BASE_ID: { "uri": root_uri } })
+    });
+
+    let log = SarifLog {
+        schema: SARIF_SCHEMA_URI,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: INFORMATION_URI,
+                    rules: rules
+                        .iter()
+                        .map(|rule| SarifRule {
+                            id: rule.id,
+                            short_description: SarifText { text: rule.summary },
+                            default_configuration: SarifConfiguration {
+                                level: sarif_level(rule.severity),
+                            },
+                        })
+                        .collect(),
+                },
+            },
+            original_uri_base_ids,
+            results,
+        }],
+    };
+
+    serde_json::to_writer_pretty(&mut *writer, &log)?;
+    writeln!(writer)
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    }
+}
+
+/// 相对路径转换为以 `/` 分隔、经过百分号编码的 URI 引用
+fn relative_path_to_uri(relative_path: &Path) -> String {
+    relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(percent_encode(&part.to_string_lossy())),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// 绝对路径转换为 `file://` URI（Windows 盘符路径转换为 `file:///C:/...`）
+fn absolute_path_to_uri(absolute_path: &Path) -> String {
+    let normalized_path = absolute_path.to_string_lossy().replace('\\', "/");
+    let encoded_segments: Vec<String> = normalized_path
+        .split('/')
+        .map(|segment| {
+            if segment.len() == 2 && segment.ends_with(':') {
+                segment.to_string()
+            } else {
+                percent_encode(segment)
+            }
+        })
+        .collect();
+    let encoded_path = encoded_segments.join("/");
+    if encoded_path.starts_with('/') {
+        format!("file://{}", encoded_path)
+    } else {
+        format!("file:///{}", encoded_path)
+    }
+}
+
+/// 对 URI 路径片段中非保留字符以外的字节做百分号编码
+fn percent_encode(segment: &str) -> String {
+    let mut encoded_segment = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded_segment.push(byte as char);
+        } else {
+            encoded_segment.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded_segment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const TEST_RULE: Rule = Rule {
+        id: "missing-name",
+        severity: Severity::Error,
+        summary: "name is required",
+    };
+
+    fn finding_at(path: &str) -> Finding {
+        let mut finding = Finding::new(&TEST_RULE, "missing name", PathBuf::from(path)).at(2, 1);
+        finding.skill = "my skill".to_string();
+        finding
+    }
+
+    #[test]
+    fn write_sarif_uses_workspace_relative_uris() {
+        let findings = vec![
+            finding_at("/repo/.claude/skills/my skill/SKILL.md"),
+            finding_at("/home/me/.claude/skills/other/SKILL.md"),
+        ];
+
+        let mut buffer = Vec::new();
+        write_sarif(&mut buffer, &[TEST_RULE], &findings, Some(Path::new("/repo")))
+            .expect("failed to write sarif");
+        let log: serde_json::Value = serde_json::from_slice(&buffer).expect("sarif is not json");
+
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "missing-name");
+        assert_eq!(run["originalUriBaseIds"]["WORKSPACE"]["uri"], "file:///repo/");
+
+        let first_location = &run["results"][0]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            first_location["artifactLocation"]["uri"],
+            ".claude/skills/my%20skill/SKILL.md"
+        );
+        assert_eq!(first_location["artifactLocation"]["uriBaseId"], "WORKSPACE");
+        assert_eq!(first_location["region"]["startLine"], 2);
+
+        let second_location = &run["results"][1]["locations"][0]["physicalLocation"];
+        assert_eq!(
+            second_location["artifactLocation"]["uri"],
+            "file:///home/me/.claude/skills/other/SKILL.md"
+        );
+        assert!(second_location["artifactLocation"].get("uriBaseId").is_none());
+        assert_eq!(run["results"][0]["ruleIndex"], 0);
+    }
+
+    #[test]
+    fn write_sarif_omits_rule_index_for_unknown_rules() {
+        let mut buffer = Vec::new();
+        write_sarif(&mut buffer, &[], &[finding_at("/repo/SKILL.md")], None).expect("failed to write sarif");
+        let log: serde_json::Value = serde_json::from_slice(&buffer).expect("sarif is not json");
+
+        let result = &log["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "missing-name");
+        assert!(result.get("ruleIndex").is_none());
+    }
+}
@@ -1,333 +1,220 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsStr;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use serde::Serialize;
 use walkdir::WalkDir;
 
+use crate::artifact::ArtifactKind;
+use crate::cache::ScanCache;
+use crate::registry::{RootBase, ToolEntry, ToolRegistry};
 use crate::skill::Skill;
 
-/// 相对路径目录配置
-#[derive(Debug, Clone, Copy)]
-struct RelativeSkillPathConfig {
+/// 未配置深度时的发现深度：根目录本身及其直接子目录
+pub const DEFAULT_MAX_DEPTH: usize = 1;
+
+/// 扫描根目录的来源类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RootKind {
+    /// 用户目录 (`~`)
+    UserHome,
+    /// 平台配置目录
+    UserConfig,
+    /// 工作区（当前目录到 git 根）
+    Workspace,
+    /// 系统级目录
+    System,
+    /// 通过 `--path` 指定的目录
+    Custom,
+}
+
+/// 一个扫描根目录
+#[derive(Debug, Clone)]
+pub struct ScanRoot {
     /// 工具名称
-    tool_name: &'static str,
-    /// 相对路径片段
-    path_parts: &'static [&'static str],
+    pub tool_name: String,
+    /// 根目录路径
+    pub path: PathBuf,
+    /// 来源类别
+    pub kind: RootKind,
+    /// 该根目录的发现深度，来自注册表条目；为 None 时使用 [`ScanOptions::max_depth`]
+    pub max_depth: Option<usize>,
+    /// 在该根目录下查找的类别
+    pub artifact_kind: ArtifactKind,
 }
 
-/// 用户目录下的主流技能路径（全平台：Windows/Linux/macOS）
-const USER_HOME_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
-    RelativeSkillPathConfig {
-        tool_name: "Claude Code",
-        path_parts: &[".claude", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "OpenAI Codex",
-        path_parts: &[".agents", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "OpenAI Codex (Legacy)",
-        path_parts: &[".codex", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Gemini CLI",
-        path_parts: &[".gemini", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Windsurf",
-        path_parts: &[".codeium", "windsurf", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "GitHub Copilot",
-        path_parts: &[".copilot", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Cursor",
-        path_parts: &[".cursor", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Cline",
-        path_parts: &[".cline", "skills"],
-    },
-    // 兼容历史目录
-    RelativeSkillPathConfig {
-        tool_name: "Gemini Antigravity (Legacy)",
-        path_parts: &[".gemini", "antigravity", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Windsurf (Legacy)",
-        path_parts: &[".windsurf", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Codeium (Legacy)",
-        path_parts: &[".codeium", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Continue (Legacy)",
-        path_parts: &[".continue", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Roo Code (Legacy)",
-        path_parts: &[".roo-code", "skills"],
-    },
-];
+/// 扫描选项
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// 排除的工具名称（不区分大小写）
+    pub exclude_tools: Vec<String>,
+    /// 排除的路径：位于其下的扫描根目录和 skills 都会被跳过
+    pub exclude_paths: Vec<PathBuf>,
+    /// 并行扫描的线程数；为 None 时使用 CPU 核数
+    pub threads: Option<usize>,
+    /// 在扫描根目录下查找 skill 目录的最大深度；为 None 时为 [`DEFAULT_MAX_DEPTH`]
+    pub max_depth: Option<usize>,
+    /// 查找时跳过的目录名（如 `node_modules`、`.git`）
+    pub ignore_directory_names: Vec<String>,
+    /// 只查找这些类别；为空时查找所有类别
+    pub kinds: Vec<ArtifactKind>,
+}
 
-/// 配置目录下的主流技能路径（遵循平台标准配置路径）
-const USER_CONFIG_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
-    RelativeSkillPathConfig {
-        tool_name: "OpenCode",
-        path_parts: &["opencode", "skills"],
-    },
-];
-
-/// 工作区目录下的技能路径（从当前目录向上查找到 git 根）
-const WORKSPACE_SKILL_PATH_CONFIGS: &[RelativeSkillPathConfig] = &[
-    // 官方路径
-    RelativeSkillPathConfig {
-        tool_name: "Claude Code (Project)",
-        path_parts: &[".claude", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "OpenAI Codex (Project)",
-        path_parts: &[".agents", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "GitHub Copilot (Project)",
-        path_parts: &[".github", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Gemini CLI (Project)",
-        path_parts: &[".gemini", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Windsurf (Project)",
-        path_parts: &[".windsurf", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Cursor (Project)",
-        path_parts: &[".cursor", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Cline (Project)",
-        path_parts: &[".cline", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Cline Compatibility (Project)",
-        path_parts: &[".clinerules", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "OpenCode (Project)",
-        path_parts: &[".opencode", "skills"],
-    },
-    // 扩展目录（Agent Skills 生态常见目录）
-    RelativeSkillPathConfig {
-        tool_name: "Antigravity (Project)",
-        path_parts: &[".agent", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Augment (Project)",
-        path_parts: &[".augment", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Codebuddy (Project)",
-        path_parts: &[".codebuddy", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "CommandCode (Project)",
-        path_parts: &[".commandcode", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Continue (Project)",
-        path_parts: &[".continue", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Crush (Project)",
-        path_parts: &[".crush", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Factory (Project)",
-        path_parts: &[".factory", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Goose (Project)",
-        path_parts: &[".goose", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "iFlow (Project)",
-        path_parts: &[".iflow", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Junie (Project)",
-        path_parts: &[".junie", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "KiloCode (Project)",
-        path_parts: &[".kilocode", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Kiro (Project)",
-        path_parts: &[".kiro", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Kode (Project)",
-        path_parts: &[".kode", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "MCP Jam (Project)",
-        path_parts: &[".mcpjam", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Mux (Project)",
-        path_parts: &[".mux", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Neovate (Project)",
-        path_parts: &[".neovate", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "OpenHands (Project)",
-        path_parts: &[".openhands", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Pi (Project)",
-        path_parts: &[".pi", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Pochi (Project)",
-        path_parts: &[".pochi", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Qoder (Project)",
-        path_parts: &[".qoder", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Qwen (Project)",
-        path_parts: &[".qwen", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Roo (Project)",
-        path_parts: &[".roo", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Trae (Project)",
-        path_parts: &[".trae", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Vibe (Project)",
-        path_parts: &[".vibe", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Zencoder (Project)",
-        path_parts: &[".zencoder", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Adal (Project)",
-        path_parts: &[".adal", "skills"],
-    },
-    // 兼容历史目录
-    RelativeSkillPathConfig {
-        tool_name: "OpenAI Codex (Legacy Project)",
-        path_parts: &[".codex", "skills"],
-    },
-    RelativeSkillPathConfig {
-        tool_name: "Roo Code (Legacy Project)",
-        path_parts: &[".roo-code", "skills"],
-    },
-];
+impl ScanOptions {
+    /// 工具是否被排除
+    pub fn is_tool_excluded(&self, tool_name: &str) -> bool {
+        self.exclude_tools
+            .iter()
+            .any(|excluded_tool| excluded_tool.eq_ignore_ascii_case(tool_name))
+    }
+
+    /// 路径是否位于某个排除路径之下
+    pub fn is_path_excluded(&self, path: &Path) -> bool {
+        self.exclude_paths
+            .iter()
+            .any(|excluded_path| path.starts_with(excluded_path))
+    }
+
+    /// 类别是否在查找范围内
+    pub fn is_kind_included(&self, artifact_kind: ArtifactKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&artifact_kind)
+    }
 
-#[cfg(not(windows))]
-const UNIX_SYSTEM_SKILL_PATHS: &[(&str, &str)] = &[("OpenAI Codex (Admin)", "/etc/codex/skills")];
+    /// 目录名是否在跳过列表中
+    pub fn is_directory_ignored(&self, directory_name: &OsStr) -> bool {
+        self.ignore_directory_names
+            .iter()
+            .any(|ignored_name| directory_name == ignored_name.as_str())
+    }
+}
 
 /// Skills 扫描器
 pub struct Scanner {
-    /// 扫描路径列表: (工具名, 路径)
-    paths: Vec<(String, PathBuf)>,
+    /// 扫描根目录列表
+    paths: Vec<ScanRoot>,
+    /// 注册表中尚不存在的候选根目录，出现后由 [`Scanner::refresh_roots`] 加入扫描
+    missing_paths: Vec<ScanRoot>,
+    /// 扫描选项
+    options: ScanOptions,
+    /// 元数据缓存；为 None 时每次都重新读取
+    cache: Option<ScanCache>,
+    /// 按 [`ScanOptions::threads`] 创建的线程池，在 [`Scanner::set_options`] 中创建一次；为 None 时使用全局线程池
+    thread_pool: Option<rayon::ThreadPool>,
 }
 
 impl Scanner {
-    /// 创建扫描器，自动添加默认扫描路径
+    /// 创建扫描器，按工具注册表（内置默认值 + 用户 tools.toml）添加默认扫描路径
+    ///
+    /// 用户 tools.toml 无法加载时静默回退到内置默认值；需要感知该错误时使用 [`Scanner::try_new`]。
     pub fn new() -> Self {
-        let mut scanner = Self { paths: Vec::new() };
-        scanner.add_default_paths();
+        Self::try_new().unwrap_or_else(|_| Self::from_registry(&ToolRegistry::builtin()))
+    }
+
+    /// 创建扫描器；用户 tools.toml 无法读取或解析时返回错误
+    ///
+    /// ```
+    /// use skills_scanner::{Error, Scanner};
+    ///
+    /// match Scanner::try_new() {
+    ///     Ok(scanner) => println!("{} 个扫描根目录", scanner.path_count()),
+    ///     Err(Error::Registry(e)) => eprintln!("tools.toml 有误: {}", e),
+    ///     Err(e) => eprintln!("{}", e),
+    /// }
+    /// ```
+    pub fn try_new() -> crate::Result<Self> {
+        Ok(Self::from_registry(&ToolRegistry::load()?))
+    }
+
+    /// 根据指定的工具注册表创建扫描器
+    pub fn from_registry(registry: &ToolRegistry) -> Self {
+        let mut scanner = Self::empty();
+        scanner.add_registry_paths(registry);
         scanner
     }
-    
-    /// 添加默认的工具 skills 目录
-    fn add_default_paths(&mut self) {
-        let mut seen_directory_paths: HashSet<PathBuf> = HashSet::new();
-        self.add_user_home_paths(&mut seen_directory_paths);
-        self.add_user_config_paths(&mut seen_directory_paths);
-        self.add_workspace_paths(&mut seen_directory_paths);
-        self.add_system_paths(&mut seen_directory_paths);
-    }
-
-    /// 添加用户目录下的 skills 路径
-    fn add_user_home_paths(&mut self, seen_directory_paths: &mut HashSet<PathBuf>) {
-        if let Some(home_directory_path) = dirs::home_dir() {
-            for path_config in USER_HOME_SKILL_PATH_CONFIGS {
-                debug_assert!(!path_config.path_parts.is_empty());
-                let candidate_path =
-                    join_path_parts(&home_directory_path, path_config.path_parts);
-                self.add_existing_directory(
-                    path_config.tool_name,
-                    candidate_path,
-                    seen_directory_paths,
-                );
-            }
+
+    /// 创建不含任何默认路径的扫描器，仅扫描之后手动添加的目录
+    pub fn empty() -> Self {
+        Self {
+            paths: Vec::new(),
+            missing_paths: Vec::new(),
+            options: ScanOptions::default(),
+            cache: None,
+            thread_pool: None,
         }
     }
 
-    /// 添加平台配置目录下的 skills 路径
-    fn add_user_config_paths(&mut self, seen_directory_paths: &mut HashSet<PathBuf>) {
-        if let Some(config_directory_path) = dirs::config_dir() {
-            for path_config in USER_CONFIG_SKILL_PATH_CONFIGS {
-                debug_assert!(!path_config.path_parts.is_empty());
-                let candidate_path =
-                    join_path_parts(&config_directory_path, path_config.path_parts);
-                self.add_existing_directory(
-                    path_config.tool_name,
-                    candidate_path,
-                    seen_directory_paths,
-                );
+    /// 设置扫描选项，并移除被排除的扫描根目录
+    ///
+    /// 线程数变化时重新创建扫描线程池；创建失败时返回错误，选项仍然生效，扫描改用全局线程池。
+    pub fn set_options(&mut self, options: ScanOptions) -> crate::Result<()> {
+        let is_included = |scan_root: &ScanRoot| {
+            options.is_kind_included(scan_root.artifact_kind)
+                && !options.is_tool_excluded(&scan_root.tool_name)
+                && !options.is_path_excluded(&scan_root.path)
+        };
+        self.paths.retain(is_included);
+        self.missing_paths.retain(is_included);
+
+        let current_thread_count = self.thread_pool.as_ref().map(rayon::ThreadPool::current_num_threads);
+        let is_thread_count_changed = options.threads != current_thread_count;
+        self.options = options;
+        if is_thread_count_changed {
+            self.thread_pool = None;
+            if let Some(thread_count) = self.options.threads {
+                self.thread_pool = Some(rayon::ThreadPoolBuilder::new().num_threads(thread_count).build()?);
             }
         }
+        Ok(())
     }
 
-    /// 添加工作区目录下的 skills 路径（当前目录向上扫描）
-    fn add_workspace_paths(&mut self, seen_directory_paths: &mut HashSet<PathBuf>) {
-        for workspace_directory_path in workspace_search_paths() {
-            for path_config in WORKSPACE_SKILL_PATH_CONFIGS {
-                debug_assert!(!path_config.path_parts.is_empty());
-                let candidate_path =
-                    join_path_parts(&workspace_directory_path, path_config.path_parts);
-                self.add_existing_directory(
-                    path_config.tool_name,
-                    candidate_path,
-                    seen_directory_paths,
-                );
-            }
-        }
+    /// 启用元数据缓存：目录指纹未变的 skill 直接使用缓存，扫描结束后写回
+    pub fn set_cache(&mut self, cache: ScanCache) {
+        self.cache = Some(cache);
     }
 
-    /// 添加系统级 skills 路径
-    fn add_system_paths(&mut self, _seen_directory_paths: &mut HashSet<PathBuf>) {
-        #[cfg(not(windows))]
-        for (tool_name, absolute_path) in UNIX_SYSTEM_SKILL_PATHS {
-            let candidate_path = PathBuf::from(absolute_path);
-            self.add_existing_directory(tool_name, candidate_path, _seen_directory_paths);
+    /// 添加注册表中所有启用工具的 skills 目录
+    fn add_registry_paths(&mut self, registry: &ToolRegistry) {
+        let mut seen_directory_paths: HashSet<PathBuf> = HashSet::new();
+
+        for (root_base, base_directory_path) in &base_directories() {
+            for tool_entry in registry.enabled_entries(*root_base) {
+                if let Ok(candidate_path) = tool_entry.resolve(base_directory_path) {
+                    self.add_existing_directory(
+                        tool_entry,
+                        candidate_path,
+                        root_base.root_kind(),
+                        &mut seen_directory_paths,
+                    );
+                }
+            }
         }
     }
 
-    /// 仅在目录存在时加入扫描列表，并做去重
+    /// 路径存在时加入扫描列表，否则记为候选根目录，并做去重
     fn add_existing_directory(
         &mut self,
-        tool_name: &str,
+        tool_entry: &ToolEntry,
         directory_path: PathBuf,
+        root_kind: RootKind,
         seen_directory_paths: &mut HashSet<PathBuf>,
     ) {
-        if !directory_path.is_dir() {
+        if !seen_directory_paths.insert(directory_path.clone()) {
             return;
         }
-        if seen_directory_paths.insert(directory_path.clone()) {
-            self.paths.push((tool_name.to_string(), directory_path));
+        let scan_root = ScanRoot {
+            tool_name: tool_entry.name.clone(),
+            path: directory_path,
+            kind: root_kind,
+            max_depth: tool_entry.max_depth,
+            artifact_kind: tool_entry.kind,
+        };
+        if scan_root.artifact_kind.root_exists(&scan_root.path) {
+            self.paths.push(scan_root);
+        } else {
+            self.missing_paths.push(scan_root);
         }
     }
     
@@ -336,62 +223,188 @@ impl Scanner {
         let is_duplicate_path = self
             .paths
             .iter()
-            .any(|(_, existing_path)| *existing_path == path);
+            .any(|scan_root| scan_root.path == path);
         if path.is_dir() && !is_duplicate_path {
-            self.paths.push(("Custom".to_string(), path));
+            self.paths.push(ScanRoot {
+                tool_name: "Custom".to_string(),
+                path,
+                kind: RootKind::Custom,
+                max_depth: None,
+                artifact_kind: ArtifactKind::Skill,
+            });
         }
     }
     
     /// 执行扫描，返回所有找到的 skills
+    ///
+    /// 各扫描根目录与其中的 skill 目录并行读取，结果按扫描根目录顺序、目录名顺序排列，与线程数无关。
     pub fn scan(&self) -> Vec<Skill> {
         let mut discovered_skills = Vec::new();
-        
-        for (tool_name, base_directory_path) in &self.paths {
-            // 支持 base_path 本身就是一个 skill 目录
-            self.collect_skill_directory(base_directory_path, tool_name, &mut discovered_skills);
-
-            // 兼容一层子目录布局：<skills>/<skill-name>/SKILL.md
-            for entry in WalkDir::new(base_directory_path)
-                .min_depth(1)
-                .max_depth(1)
-                .follow_links(true)
-                .into_iter()
-                .filter_map(|e| e.ok())
-            {
-                let skill_directory_path = entry.path();
-                if skill_directory_path.is_dir() {
-                    self.collect_skill_directory(
-                        skill_directory_path,
-                        tool_name,
-                        &mut discovered_skills,
-                    );
+        self.scan_each(|skill| {
+            discovered_skills.push(skill);
+            ControlFlow::Continue(())
+        });
+        discovered_skills
+    }
+
+    /// 执行扫描，按与 [`Scanner::scan`] 相同的顺序逐个回调 skills，不等待整个扫描结束
+    ///
+    /// 排在最前面、尚未完成的扫描根目录中的 skill 读取到后立即回调；排在后面的根目录即使先完成，
+    /// 其 skills 也要暂存到前面的根目录都完成后才回调。某个根目录很慢（如网络挂载）时，
+    /// 它之后的所有输出都会随之推迟，这是保证输出顺序与线程数无关的代价。
+    ///
+    /// 回调返回 [`ControlFlow::Break`] 时停止扫描：不再回调，尚未开始的查找与读取也不再进行。
+    pub fn scan_each<F: FnMut(Skill) -> ControlFlow<()>>(&self, mut on_skill: F) {
+        let is_cancelled = AtomicBool::new(false);
+        self.in_scan_scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+            for (root_index, scan_root) in self.paths.iter().enumerate() {
+                let sender = sender.clone();
+                let is_cancelled = &is_cancelled;
+                scope.spawn(move |scope| self.scan_root(scope, root_index, scan_root, sender, is_cancelled));
+            }
+            drop(sender);
+
+            let mut ordered_skills = OrderedSkills::new(self.paths.len());
+            'receive: for scan_event in receiver {
+                ordered_skills.record(scan_event);
+                while let Some(skill) = ordered_skills.next_ready() {
+                    if on_skill(skill).is_break() {
+                        is_cancelled.store(true, Ordering::Relaxed);
+                        break 'receive;
+                    }
                 }
             }
+        });
+
+        // 缓存只是加速手段，写入失败时下次扫描重新读取即可
+        if let Some(cache) = &self.cache {
+            let _ = cache.save();
+        }
+    }
+
+    /// 在按 `threads` 配置的线程池中运行扫描任务
+    fn in_scan_scope<'scope, R>(&self, op: impl FnOnce(&rayon::Scope<'scope>) -> R) -> R {
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.in_place_scope(op),
+            None => rayon::in_place_scope(op),
         }
-        
-        discovered_skills
     }
 
-    /// 将符合条件的目录转换为 Skill
-    fn collect_skill_directory(
-        &self,
-        skill_directory_path: &Path,
-        tool_name: &str,
-        discovered_skills: &mut Vec<Skill>,
+    /// 扫描单个根目录：根目录本身及发现深度内的 skills（或规则文件），按名称深度优先编号
+    ///
+    /// 含 `SKILL.md` 的子目录视为 skill，不再向下查找；其余目录视为分类目录继续查找。
+    /// 其他类别的根目录可以是单个文件，也可以是包含规则文件的目录。
+    /// 每发现一个候选路径就交给线程池读取，结果连同编号发回；查找完毕后发回候选路径总数。
+    /// `is_cancelled` 置位后停止查找，尚未开始的读取直接跳过。
+    fn scan_root<'scope>(
+        &'scope self,
+        scope: &rayon::Scope<'scope>,
+        root_index: usize,
+        scan_root: &'scope ScanRoot,
+        sender: mpsc::Sender<ScanEvent>,
+        is_cancelled: &'scope AtomicBool,
     ) {
-        if !skill_directory_path.join("SKILL.md").is_file() {
-            return;
+        let max_depth = self.discovery_depth(scan_root);
+        let extractor = scan_root.artifact_kind.extractor();
+
+        let mut candidate_count = 0;
+        let mut load_candidate = |artifact_path: PathBuf| {
+            let sender = sender.clone();
+            let position = candidate_count;
+            candidate_count += 1;
+            scope.spawn(move |_| {
+                if is_cancelled.load(Ordering::Relaxed) {
+                    return;
+                }
+                let skill = self.load_artifact(artifact_path, scan_root).map(Box::new);
+                let _ = sender.send(ScanEvent::Loaded { root_index, position, skill });
+            });
+        };
+
+        // 支持 base_path 本身就是一个 skill 目录（或规则文件）；其下的子目录仍然继续查找
+        load_candidate(scan_root.path.clone());
+
+        // <skills>/<skill-name>/SKILL.md，以及 <skills>/<category>/.../<skill-name>/SKILL.md
+        let mut directory_walker = WalkDir::new(&scan_root.path)
+            .min_depth(1)
+            .max_depth(max_depth)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter();
+        while let Some(entry) = directory_walker.next() {
+            if is_cancelled.load(Ordering::Relaxed) {
+                return;
+            }
+            let Ok(entry) = entry else {
+                continue;
+            };
+            let is_directory = entry.file_type().is_dir();
+            if is_directory && self.options.is_directory_ignored(entry.file_name()) {
+                directory_walker.skip_current_dir();
+                continue;
+            }
+            if extractor.is_artifact(entry.path()) {
+                if is_directory {
+                    directory_walker.skip_current_dir();
+                }
+                load_candidate(entry.into_path());
+            }
         }
-        if let Some(discovered_skill) = Skill::from_path(skill_directory_path.to_path_buf(), tool_name)
-        {
-            discovered_skills.push(discovered_skill);
+
+        let _ = sender.send(ScanEvent::Discovered { root_index, candidate_count });
+    }
+
+    /// 将符合该根目录类别的目录或文件转换为 Skill
+    fn load_artifact(&self, artifact_path: PathBuf, scan_root: &ScanRoot) -> Option<Skill> {
+        let extractor = scan_root.artifact_kind.extractor();
+        if !extractor.is_artifact(&artifact_path) || self.options.is_path_excluded(&artifact_path) {
+            return None;
         }
+        let metadata = match &self.cache {
+            Some(cache) => cache.load_metadata(&artifact_path, scan_root.artifact_kind),
+            None => extractor.read_metadata(&artifact_path),
+        };
+        Skill::with_metadata(artifact_path, scan_root, metadata)
     }
-    
+
+    /// 根目录的发现深度：注册表条目的设置优先，其次为扫描选项，默认 [`DEFAULT_MAX_DEPTH`]
+    pub fn discovery_depth(&self, scan_root: &ScanRoot) -> usize {
+        scan_root
+            .max_depth
+            .or(self.options.max_depth)
+            .unwrap_or(DEFAULT_MAX_DEPTH)
+    }
+
+    /// 获取扫描选项
+    pub fn options(&self) -> &ScanOptions {
+        &self.options
+    }
+
     /// 获取扫描路径数量
     pub fn path_count(&self) -> usize {
         self.paths.len()
     }
+
+    /// 获取所有扫描根目录
+    pub fn roots(&self) -> &[ScanRoot] {
+        &self.paths
+    }
+
+    /// 获取尚不存在的候选根目录
+    pub fn missing_roots(&self) -> &[ScanRoot] {
+        &self.missing_paths
+    }
+
+    /// 将已经出现的候选根目录加入扫描列表（追加在末尾），返回新加入的根目录
+    pub fn refresh_roots(&mut self) -> Vec<ScanRoot> {
+        let (appeared_roots, missing_roots): (Vec<ScanRoot>, Vec<ScanRoot>) = std::mem::take(&mut self.missing_paths)
+            .into_iter()
+            .partition(|scan_root| scan_root.artifact_kind.root_exists(&scan_root.path));
+        self.missing_paths = missing_roots;
+        self.paths.extend(appeared_roots.iter().cloned());
+        appeared_roots
+    }
 }
 
 impl Default for Scanner {
@@ -400,18 +413,33 @@ impl Default for Scanner {
     }
 }
 
-/// 将路径片段拼接到基础路径上
-fn join_path_parts(base_path: &Path, path_parts: &[&str]) -> PathBuf {
-    debug_assert!(!path_parts.is_empty());
-    let mut full_path = base_path.to_path_buf();
-    for path_part in path_parts {
-        full_path.push(path_part);
+/// 注册表路径的基准目录：用户目录、平台配置目录、工作区各级目录、系统绝对路径（空路径）
+pub fn base_directories() -> Vec<(RootBase, PathBuf)> {
+    let mut base_directories: Vec<(RootBase, PathBuf)> = Vec::new();
+    if let Some(home_directory_path) = dirs::home_dir() {
+        base_directories.push((RootBase::Home, home_directory_path));
+    }
+    if let Some(config_directory_path) = dirs::config_dir() {
+        base_directories.push((RootBase::Config, config_directory_path));
+    }
+    for workspace_directory_path in workspace_search_paths() {
+        base_directories.push((RootBase::Workspace, workspace_directory_path));
+    }
+    base_directories.push((RootBase::Absolute, PathBuf::new()));
+    base_directories
+}
+
+/// 获取工作区根目录：找到 git 根时为 git 根，否则为当前目录
+pub fn workspace_root() -> Option<PathBuf> {
+    let search_paths = workspace_search_paths();
+    match search_paths.last() {
+        Some(last_path) if last_path.join(".git").exists() => Some(last_path.clone()),
+        _ => search_paths.into_iter().next(),
     }
-    full_path
 }
 
 /// 获取从当前目录到 git 根目录的路径链（包含当前目录和 git 根）
-fn workspace_search_paths() -> Vec<PathBuf> {
+pub fn workspace_search_paths() -> Vec<PathBuf> {
     let current_directory_path = match std::env::current_dir() {
         Ok(path) => path,
         Err(_) => return Vec::new(),
@@ -428,27 +456,75 @@ fn workspace_search_paths() -> Vec<PathBuf> {
     ancestor_paths
 }
 
+/// 扫描线程发回的进度
+enum ScanEvent {
+    /// 某个扫描根目录中编号为 `position` 的候选路径已读取；不是有效 skill 时为 None
+    Loaded {
+        root_index: usize,
+        position: usize,
+        skill: Option<Box<Skill>>,
+    },
+    /// 某个扫描根目录已查找完毕，共 `candidate_count` 个候选路径
+    Discovered { root_index: usize, candidate_count: usize },
+}
+
+/// 将乱序到达的读取结果整理为按扫描根目录、再按候选路径编号排列的顺序
+struct OrderedSkills {
+    /// 每个扫描根目录已读取但尚未释放的结果，键为候选路径编号
+    pending_skills: Vec<BTreeMap<usize, Option<Box<Skill>>>>,
+    /// 每个扫描根目录的候选路径总数；查找尚未完毕时为 None
+    candidate_counts: Vec<Option<usize>>,
+    /// 正在释放的扫描根目录
+    next_root_index: usize,
+    /// 该根目录中下一个要释放的候选路径编号
+    next_position: usize,
+}
+
+impl OrderedSkills {
+    fn new(root_count: usize) -> Self {
+        Self {
+            pending_skills: (0..root_count).map(|_| BTreeMap::new()).collect(),
+            candidate_counts: vec![None; root_count],
+            next_root_index: 0,
+            next_position: 0,
+        }
+    }
+
+    fn record(&mut self, scan_event: ScanEvent) {
+        match scan_event {
+            ScanEvent::Loaded { root_index, position, skill } => {
+                self.pending_skills[root_index].insert(position, skill);
+            }
+            ScanEvent::Discovered { root_index, candidate_count } => {
+                self.candidate_counts[root_index] = Some(candidate_count);
+            }
+        }
+    }
+
+    /// 取出下一个可以按顺序释放的 skill；前面还有未读取的候选路径时返回 None
+    fn next_ready(&mut self) -> Option<Skill> {
+        while self.next_root_index < self.pending_skills.len() {
+            if let Some(loaded_skill) = self.pending_skills[self.next_root_index].remove(&self.next_position) {
+                self.next_position += 1;
+                if let Some(skill) = loaded_skill {
+                    return Some(*skill);
+                }
+            } else if self.candidate_counts[self.next_root_index] == Some(self.next_position) {
+                self.next_root_index += 1;
+                self.next_position = 0;
+            } else {
+                return None;
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::create_temp_directory;
     use std::fs;
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    fn create_temp_directory(suffix: &str) -> PathBuf {
-        let timestamp_nanos = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system time is earlier than UNIX_EPOCH")
-            .as_nanos();
-        let temp_directory_path = std::env::temp_dir().join(format!(
-            "skills-scanner-test-{}-{}-{}",
-            suffix,
-            std::process::id(),
-            timestamp_nanos
-        ));
-        fs::create_dir_all(&temp_directory_path)
-            .expect("failed to create temporary test directory");
-        temp_directory_path
-    }
 
     fn write_skill_markdown(skill_directory_path: &Path) {
         let skill_markdown_path = skill_directory_path.join("SKILL.md");
@@ -474,7 +550,17 @@ mod tests {
         write_skill_markdown(&nested_skill_directory_path);
 
         let scanner = Scanner {
-            paths: vec![("TestTool".to_string(), temp_directory_path.clone())],
+            paths: vec![ScanRoot {
+                tool_name: "TestTool".to_string(),
+                path: temp_directory_path.clone(),
+                kind: RootKind::Custom,
+                max_depth: None,
+                artifact_kind: ArtifactKind::Skill,
+            }],
+            missing_paths: Vec::new(),
+            options: ScanOptions::default(),
+            cache: None,
+            thread_pool: None,
         };
         let found_skills = scanner.scan();
 
@@ -487,12 +573,46 @@ mod tests {
         fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
     }
 
+    #[test]
+    fn set_options_excludes_tools_and_paths() {
+        let temp_directory_path = create_temp_directory("scan-exclude");
+        let kept_skill_directory_path = temp_directory_path.join("kept-skill");
+        let excluded_skill_directory_path = temp_directory_path.join("excluded-skill");
+        fs::create_dir_all(&kept_skill_directory_path).expect("failed to create kept skill directory");
+        fs::create_dir_all(&excluded_skill_directory_path)
+            .expect("failed to create excluded skill directory");
+        write_skill_markdown(&kept_skill_directory_path);
+        write_skill_markdown(&excluded_skill_directory_path);
+
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(temp_directory_path.clone());
+        scanner
+            .set_options(ScanOptions {
+                exclude_paths: vec![excluded_skill_directory_path],
+                ..ScanOptions::default()
+            })
+            .expect("failed to set scan options");
+        let found_skills = scanner.scan();
+        assert_eq!(found_skills.len(), 1);
+        assert_eq!(found_skills[0].name, "kept-skill");
+
+        scanner
+            .set_options(ScanOptions {
+                exclude_tools: vec!["custom".to_string()],
+                ..ScanOptions::default()
+            })
+            .expect("failed to set scan options");
+        assert_eq!(scanner.path_count(), 0);
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
     #[test]
     fn add_custom_path_ignores_duplicates_and_missing_directories() {
         let temp_directory_path = create_temp_directory("custom-path");
         let missing_directory_path = temp_directory_path.join("missing-directory");
 
-        let mut scanner = Scanner { paths: Vec::new() };
+        let mut scanner = Scanner::empty();
         scanner.add_custom_path(temp_directory_path.clone());
         scanner.add_custom_path(temp_directory_path.clone());
         scanner.add_custom_path(missing_directory_path);
@@ -501,4 +621,238 @@ mod tests {
 
         fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
     }
+
+    #[test]
+    fn parallel_scan_order_is_stable_across_thread_counts() {
+        let temp_directory_path = create_temp_directory("scan-parallel");
+        let mut scanner = Scanner::empty();
+        for root_name in ["root-b", "root-a", "root-c"] {
+            let root_path = temp_directory_path.join(root_name);
+            for skill_name in ["zeta", "alpha", "mid"] {
+                let skill_directory_path = root_path.join(skill_name);
+                fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+                write_skill_markdown(&skill_directory_path);
+            }
+            scanner.add_custom_path(root_path);
+        }
+
+        let scan_paths = |thread_count: usize, scanner: &mut Scanner| -> Vec<PathBuf> {
+            scanner
+                .set_options(ScanOptions {
+                    threads: Some(thread_count),
+                    ..ScanOptions::default()
+                })
+                .expect("failed to set scan options");
+            scanner.scan().into_iter().map(|skill| skill.path).collect()
+        };
+        let sequential_paths = scan_paths(1, &mut scanner);
+        assert_eq!(sequential_paths.len(), 9);
+        assert!(sequential_paths[0].ends_with("root-b/alpha"));
+        assert!(sequential_paths[8].ends_with("root-c/zeta"));
+        for _ in 0..5 {
+            assert_eq!(scan_paths(8, &mut scanner), sequential_paths);
+        }
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
+    #[test]
+    fn scan_each_stops_when_the_callback_breaks() {
+        let temp_directory_path = create_temp_directory("scan-each-break");
+        let mut scanner = Scanner::empty();
+        for root_name in ["root-a", "root-b"] {
+            let root_path = temp_directory_path.join(root_name);
+            for skill_name in ["alpha", "beta", "gamma"] {
+                let skill_directory_path = root_path.join(skill_name);
+                fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+                write_skill_markdown(&skill_directory_path);
+            }
+            scanner.add_custom_path(root_path);
+        }
+
+        let mut visited_paths = Vec::new();
+        scanner.scan_each(|skill| {
+            visited_paths.push(skill.path);
+            if visited_paths.len() == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+
+        assert_eq!(
+            visited_paths,
+            vec![
+                temp_directory_path.join("root-a").join("alpha"),
+                temp_directory_path.join("root-a").join("beta"),
+            ]
+        );
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
+    #[test]
+    fn ordered_skills_release_the_first_unfinished_root_while_it_is_scanned() {
+        let temp_directory_path = create_temp_directory("scan-ordered-release");
+        let scan_root = ScanRoot {
+            tool_name: "TestTool".to_string(),
+            path: temp_directory_path.clone(),
+            kind: RootKind::Custom,
+            max_depth: None,
+            artifact_kind: ArtifactKind::Skill,
+        };
+        let load_skill = |skill_name: &str| {
+            let skill_directory_path = temp_directory_path.join(skill_name);
+            fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+            write_skill_markdown(&skill_directory_path);
+            Skill::from_path(skill_directory_path, &scan_root).map(Box::new)
+        };
+        let loaded = |root_index: usize, position: usize, skill: Option<Box<Skill>>| ScanEvent::Loaded {
+            root_index,
+            position,
+            skill,
+        };
+        let mut ordered_skills = OrderedSkills::new(2);
+        let released_paths = |ordered_skills: &mut OrderedSkills| -> Vec<PathBuf> {
+            std::iter::from_fn(|| ordered_skills.next_ready())
+                .map(|skill| skill.path)
+                .collect()
+        };
+
+        // 第二个根目录先完成，但要等第一个根目录结束才释放
+        ordered_skills.record(loaded(1, 0, load_skill("later")));
+        ordered_skills.record(ScanEvent::Discovered { root_index: 1, candidate_count: 1 });
+        assert!(released_paths(&mut ordered_skills).is_empty());
+
+        // 第一个根目录仍在查找，已读取的前缀立即释放，缺口之后的暂存
+        ordered_skills.record(loaded(0, 0, None));
+        ordered_skills.record(loaded(0, 2, load_skill("third")));
+        ordered_skills.record(loaded(0, 1, load_skill("second")));
+        assert_eq!(
+            released_paths(&mut ordered_skills),
+            vec![temp_directory_path.join("second"), temp_directory_path.join("third")]
+        );
+
+        ordered_skills.record(ScanEvent::Discovered { root_index: 0, candidate_count: 3 });
+        assert_eq!(released_paths(&mut ordered_skills), vec![temp_directory_path.join("later")]);
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
+    #[test]
+    fn nested_categories_are_found_within_max_depth_and_ignored_directories_skipped() {
+        let temp_directory_path = create_temp_directory("scan-nested");
+        for relative_path in [
+            "top-level",
+            "frontend/dom-cleanup",
+            "frontend/dom-cleanup/examples/inner",
+            "frontend/react/hooks",
+            "node_modules/vendored",
+        ] {
+            let skill_directory_path = temp_directory_path.join(relative_path);
+            fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+            write_skill_markdown(&skill_directory_path);
+        }
+
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(temp_directory_path.clone());
+        let scan_names = |scanner: &Scanner| -> Vec<String> {
+            scanner.scan().iter().map(Skill::qualified_name).collect()
+        };
+        assert_eq!(scan_names(&scanner), vec!["top-level"]);
+
+        scanner
+            .set_options(ScanOptions {
+                max_depth: Some(3),
+                ignore_directory_names: vec!["node_modules".to_string()],
+                ..ScanOptions::default()
+            })
+            .expect("failed to set scan options");
+        // skill 目录内部不再向下查找
+        assert_eq!(
+            scan_names(&scanner),
+            vec!["frontend/dom-cleanup", "frontend/react/hooks", "top-level"]
+        );
+        let found_skills = scanner.scan();
+        assert_eq!(found_skills[1].category.as_deref(), Some("frontend/react"));
+        assert_eq!(found_skills[2].category, None);
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
+    #[test]
+    fn rule_roots_find_rule_files_and_kind_filter_applies() {
+        let temp_directory_path = create_temp_directory("scan-rules");
+        let rules_root_path = temp_directory_path.join("rules");
+        fs::create_dir_all(rules_root_path.join("frontend")).expect("failed to create rules directory");
+        fs::write(rules_root_path.join("general.mdc"), "---\nalwaysApply: true\n---\n通用约定\n")
+            .expect("failed to write rule");
+        fs::write(rules_root_path.join("frontend").join("react.mdc"), "---\nglobs: src/**/*.tsx\n---\n")
+            .expect("failed to write rule");
+        fs::write(rules_root_path.join("README.md"), "不是规则").expect("failed to write README.md");
+        let registry_file_path = temp_directory_path.join("tools.toml");
+        fs::write(
+            &registry_file_path,
+            format!(
+                "[[tools]]\nname = \"Team Rules\"\nroot = \"absolute\"\npath = {:?}\nkind = \"cursor-rule\"\nmax_depth = 2\n",
+                rules_root_path.display().to_string()
+            ),
+        )
+        .expect("failed to write tools.toml");
+        let mut registry = ToolRegistry::builtin();
+        registry.merge_file(&registry_file_path).expect("failed to merge registry");
+        let scan_team_rules = |scanner: &Scanner| -> Vec<Skill> {
+            scanner
+                .scan()
+                .into_iter()
+                .filter(|skill| skill.tool == "Team Rules")
+                .collect()
+        };
+
+        let mut scanner = Scanner::from_registry(&registry);
+        let found_rules = scan_team_rules(&scanner);
+        let rule_names: Vec<String> = found_rules.iter().map(Skill::qualified_name).collect();
+        assert_eq!(rule_names, vec!["frontend/react", "general"]);
+        assert!(found_rules.iter().all(|rule| rule.kind == ArtifactKind::CursorRule));
+        assert!(found_rules[1].rule.as_ref().is_some_and(|rule| rule.always_apply));
+
+        scanner
+            .set_options(ScanOptions {
+                kinds: vec![ArtifactKind::Skill],
+                ..ScanOptions::default()
+            })
+            .expect("failed to set scan options");
+        assert!(scan_team_rules(&scanner).is_empty());
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
+
+    #[test]
+    fn refresh_roots_moves_appeared_registry_roots_into_scan_list() {
+        let temp_directory_path = create_temp_directory("scan-refresh");
+        let team_root_path = temp_directory_path.join("team").join("skills");
+        let registry_file_path = temp_directory_path.join("tools.toml");
+        fs::write(
+            &registry_file_path,
+            format!(
+                "[[tools]]\nname = \"Team\"\nroot = \"absolute\"\npath = {:?}\n",
+                team_root_path.display().to_string()
+            ),
+        )
+        .expect("failed to write tools.toml");
+        let mut registry = ToolRegistry::builtin();
+        registry.merge_file(&registry_file_path).expect("failed to merge registry");
+
+        let mut scanner = Scanner::from_registry(&registry);
+        assert!(scanner.missing_roots().iter().any(|scan_root| scan_root.path == team_root_path));
+        assert!(scanner.refresh_roots().iter().all(|scan_root| scan_root.path != team_root_path));
+
+        fs::create_dir_all(&team_root_path).expect("failed to create team root");
+        let appeared_roots = scanner.refresh_roots();
+        assert!(appeared_roots.iter().any(|scan_root| scan_root.path == team_root_path));
+        assert!(scanner.roots().iter().any(|scan_root| scan_root.path == team_root_path));
+        assert!(scanner.missing_roots().iter().all(|scan_root| scan_root.path != team_root_path));
+
+        fs::remove_dir_all(temp_directory_path).expect("failed to clean up temp directory");
+    }
 }
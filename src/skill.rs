@@ -1,70 +1,299 @@
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::fs;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
 
-/// 表示一个 skill 的数据结构
+use crate::artifact::{ArtifactKind, ClaudeExtension, RuleActivation};
+use crate::content;
+use crate::link;
+use crate::manifest::{split_frontmatter, SkillManifest};
+use crate::scanner::{RootKind, ScanRoot};
+
+/// [`Skill::id`] 的长度
+const SKILL_ID_LENGTH: usize = 12;
+
+/// skills 列表的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    /// 保持扫描顺序
+    Scan,
+    /// 按名称
+    Name,
+    /// 按工具名称，其次按名称
+    Tool,
+    /// 按路径
+    Path,
+    /// 按分类路径（没有分类的在前），其次按名称
+    Category,
+}
+
+/// 按指定方式对 skills 排序（稳定排序）
+pub fn sort_skills(skills: &mut [Skill], sort_key: SortKey) {
+    match sort_key {
+        SortKey::Scan => {}
+        SortKey::Name => skills.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.tool.cmp(&b.tool))),
+        SortKey::Tool => skills.sort_by(|a, b| a.tool.cmp(&b.tool).then_with(|| a.name.cmp(&b.name))),
+        SortKey::Path => skills.sort_by(|a, b| a.path.cmp(&b.path)),
+        SortKey::Category => skills.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.tool.cmp(&b.tool))
+        }),
+    }
+}
+
+/// 同名 skills 的分组
+pub struct SkillGroup {
+    pub name: String,
+    /// 组内 skills 在原列表中的下标
+    pub indices: Vec<usize>,
+}
+
+/// 按类别与名称分组 skills（先按类别、再按名称排序），同名的 skill 与命令不会合并
+pub fn group_skills(skills: &[Skill], indices: &[usize]) -> Vec<SkillGroup> {
+    let mut map: BTreeMap<(ArtifactKind, String), Vec<usize>> = BTreeMap::new();
+    for &idx in indices {
+        let key = (skills[idx].kind, skills[idx].name.clone());
+        map.entry(key).or_default().push(idx);
+    }
+    map.into_iter()
+        .map(|((_, name), indices)| SkillGroup { name, indices })
+        .collect()
+}
+
+/// 按真实路径去重：链接与其指向的目录视为同一份，优先保留真实目录，其余按原顺序
+pub fn dedupe_by_real_path<'a>(skills: impl IntoIterator<Item = &'a Skill>) -> Vec<&'a Skill> {
+    let mut skills: Vec<&Skill> = skills.into_iter().collect();
+    skills.sort_by_key(|skill| skill.link_target.is_some());
+    let mut seen_real_paths = HashSet::new();
+    skills
+        .into_iter()
+        .filter(|skill| seen_real_paths.insert(fs::canonicalize(&skill.path).unwrap_or_else(|_| skill.path.clone())))
+        .collect()
+}
+
+/// 单个 skill 的 JSON 表示，与内部 `Skill` 结构解耦以保持字段稳定
+///
+/// `--format json`、MCP 工具与 HTTP API 共用这一表示，`id` 在各处一致。
+#[derive(Debug, Serialize)]
+pub struct SkillRecord<'a> {
+    id: String,
+    name: &'a str,
+    kind: ArtifactKind,
+    tool: &'a str,
+    category: Option<&'a str>,
+    path: String,
+    description: Option<&'a str>,
+    manifest: Option<&'a SkillManifest>,
+    root: String,
+    root_kind: RootKind,
+    link_target: Option<String>,
+    /// 仅 Cursor 规则有该字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<&'a RuleActivation>,
+    /// 仅 Claude Code 命令与子代理有该字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extension: Option<&'a ClaudeExtension>,
+    content_hash: Option<&'a str>,
+}
+
+impl<'a> SkillRecord<'a> {
+    pub fn new(skill: &'a Skill) -> Self {
+        Self {
+            id: skill.id(),
+            name: &skill.name,
+            kind: skill.kind,
+            tool: &skill.tool,
+            category: skill.category.as_deref(),
+            path: skill.path.display().to_string(),
+            description: skill.description.as_deref(),
+            manifest: skill.manifest.as_ref(),
+            root: skill.root.display().to_string(),
+            root_kind: skill.root_kind,
+            link_target: skill
+                .link_target
+                .as_ref()
+                .map(|link_target_path| link_target_path.display().to_string()),
+            rule: skill.rule.as_ref(),
+            extension: skill.extension.as_ref(),
+            content_hash: skill.content_hash.as_deref(),
+        }
+    }
+}
+
+/// 需要读取 skill 目录内容才能得到的信息，可被扫描缓存复用
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SkillMetadata {
+    pub description: Option<String>,
+    pub manifest: Option<SkillManifest>,
+    /// 规则文件的生效方式（仅 Cursor 规则）
+    pub rule: Option<RuleActivation>,
+    /// Claude Code 命令与子代理的设置
+    pub extension: Option<ClaudeExtension>,
+    pub content_hash: Option<String>,
+}
+
+impl SkillMetadata {
+    /// 读取 SKILL.md 并计算目录内容哈希
+    pub fn read(path: &Path) -> Self {
+        let content = fs::read_to_string(path.join("SKILL.md")).ok();
+        let manifest = content
+            .as_deref()
+            .and_then(|content| SkillManifest::parse(content).ok());
+        let description = content
+            .as_deref()
+            .and_then(|content| Skill::extract_description(content, manifest.as_ref()));
+        Self {
+            description,
+            manifest,
+            rule: None,
+            extension: None,
+            content_hash: content::hash_directory(path).ok(),
+        }
+    }
+}
+
+/// 表示一个 skill 的数据结构；也用于表示规则、指令文件等其他类别的扫描结果（见 [`ArtifactKind`]）
 #[derive(Debug, Clone)]
 pub struct Skill {
     /// Skill 名称
     pub name: String,
+    /// 类别
+    pub kind: ArtifactKind,
     /// 所属工具名称 (Cursor, Claude, Gemini 等)
     pub tool: String,
+    /// 扫描根目录与 skill 目录之间的分类路径（如 `frontend` 或 `frontend/dom`），直接位于根目录下时为 None
+    pub category: Option<String>,
     /// Skill 目录的完整路径
     pub path: PathBuf,
     /// 从 SKILL.md 提取的描述
     pub description: Option<String>,
+    /// 从 SKILL.md frontmatter 解析出的清单
+    pub manifest: Option<SkillManifest>,
+    /// 发现该 skill 的扫描根目录
+    pub root: PathBuf,
+    /// 扫描根目录的来源类别
+    pub root_kind: RootKind,
+    /// skill 目录本身是链接时，指向的规范副本
+    pub link_target: Option<PathBuf>,
+    /// 规则文件的生效方式（仅 Cursor 规则）
+    pub rule: Option<RuleActivation>,
+    /// Claude Code 命令与子代理的 frontmatter 设置
+    pub extension: Option<ClaudeExtension>,
+    /// 整个目录树的 SHA-256，目录无法读取时为 None
+    pub content_hash: Option<String>,
 }
 
 impl Skill {
-    /// 从目录路径创建 Skill
-    pub fn from_path(path: PathBuf, tool: &str) -> Option<Self> {
-        let name = path.file_name()?.to_string_lossy().to_string();
-        let description = Self::extract_description(&path);
-        
+    /// 从目录（或规则文件）路径创建 Skill，按扫描根目录的类别读取元数据
+    pub fn from_path(path: PathBuf, scan_root: &ScanRoot) -> Option<Self> {
+        let metadata = scan_root.artifact_kind.extractor().read_metadata(&path);
+        Self::with_metadata(path, scan_root, metadata)
+    }
+
+    /// 使用已读取（或缓存）的元数据创建 Skill
+    pub fn with_metadata(path: PathBuf, scan_root: &ScanRoot, metadata: SkillMetadata) -> Option<Self> {
+        let name = scan_root.artifact_kind.extractor().artifact_name(&path)?;
+        let link_target = link::read_link_target(&path);
+        let category = path
+            .parent()
+            .and_then(|parent_path| parent_path.strip_prefix(&scan_root.path).ok())
+            .filter(|category_path| !category_path.as_os_str().is_empty())
+            .map(|category_path| {
+                category_path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            });
+
         Some(Self {
             name,
-            tool: tool.to_string(),
+            kind: scan_root.artifact_kind,
+            tool: scan_root.tool_name.clone(),
+            category,
             path,
-            description,
+            description: metadata.description,
+            manifest: metadata.manifest,
+            root: scan_root.path.clone(),
+            root_kind: scan_root.kind,
+            link_target,
+            rule: metadata.rule,
+            extension: metadata.extension,
+            content_hash: metadata.content_hash,
         })
     }
     
-    /// 从 SKILL.md 文件提取描述
-    fn extract_description(skill_path: &PathBuf) -> Option<String> {
-        let skill_md = skill_path.join("SKILL.md");
-        if !skill_md.exists() {
-            return None;
+    /// 带分类路径的名称（如 `frontend/dom-cleanup`），没有分类时即为名称
+    pub fn qualified_name(&self) -> String {
+        match &self.category {
+            Some(category) => format!("{}/{}", category, self.name),
+            None => self.name.clone(),
         }
-        
-        let content = fs::read_to_string(&skill_md).ok()?;
-        
-        // 尝试从 YAML frontmatter 提取 description
-        if content.starts_with("---") {
-            let parts: Vec<&str> = content.splitn(3, "---").collect();
-            if parts.len() >= 3 {
-                for line in parts[1].lines() {
-                    let line = line.trim();
-                    if line.starts_with("description:") {
-                        let desc = line.strip_prefix("description:")?.trim();
-                        // 移除可能的引号
-                        let desc = desc.trim_matches('"').trim_matches('\'');
-                        return Some(desc.to_string());
-                    }
-                }
-            }
+    }
+
+    /// 由路径派生的稳定标识（路径 SHA-256 的前 12 位十六进制），供 MCP 等外部调用方引用
+    pub fn id(&self) -> String {
+        let path_hash = content::to_hex(&Sha256::digest(self.path.to_string_lossy().as_bytes()));
+        path_hash[..SKILL_ID_LENGTH].to_string()
+    }
+
+    /// 主文件：skill 为其中的 `SKILL.md`，其他类别即文件本身
+    pub fn main_file_path(&self) -> PathBuf {
+        match self.kind {
+            ArtifactKind::Skill => self.path.join("SKILL.md"),
+            _ => self.path.clone(),
+        }
+    }
+
+    /// 目录中所有文件的相对路径（`/` 分隔，按路径排序）；单文件类别即其文件名
+    pub fn list_files(&self) -> Vec<String> {
+        if self.path.is_file() {
+            return self
+                .path
+                .file_name()
+                .map(|file_name| vec![file_name.to_string_lossy().to_string()])
+                .unwrap_or_default();
+        }
+        WalkDir::new(&self.path)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let relative_path = entry.path().strip_prefix(&self.path).ok()?;
+                Some(
+                    relative_path
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                )
+            })
+            .collect()
+    }
+
+    /// 提取描述：优先使用 frontmatter 中的 description
+    pub(crate) fn extract_description(content: &str, manifest: Option<&SkillManifest>) -> Option<String> {
+        if let Some(description) = manifest.and_then(|manifest| manifest.description.clone()) {
+            return Some(description);
         }
         
-        // 如果没有 frontmatter，取第一个非空行作为描述
-        content.lines()
-            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
-            .next()
+        // 如果没有 description，取正文第一个非空、非标题行作为描述
+        let body = split_frontmatter(content)
+            .map(|frontmatter| frontmatter.body)
+            .unwrap_or(content);
+        body.lines()
+            .find(|line| !line.trim().is_empty() && !line.starts_with('#'))
             .map(|s| s.trim().to_string())
     }
     
-    /// 格式化显示
-    pub fn display_name(&self) -> String {
-        format!("{:<12} > {}", self.tool, self.name)
-    }
-    
     /// 获取描述，如果没有则返回默认值
     pub fn display_description(&self) -> &str {
         self.description.as_deref().unwrap_or("No description")
@@ -0,0 +1,252 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::content::{self, FileDiff};
+use crate::fs_util;
+use crate::skill::{self, Skill};
+use crate::trash::DeleteMode;
+
+/// 单个副本的同步结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncStatus {
+    /// 内容已与源一致，无需处理
+    UpToDate,
+    /// dry-run 或待确认：将被源覆盖
+    Planned,
+    /// 已用源的内容替换
+    Synced,
+    /// 同步失败
+    Failed,
+}
+
+/// 同步计划与结果记录；`diff` 以目标副本为基准，描述同步后将发生的文件变化
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncOutcome {
+    pub name: String,
+    pub tool: String,
+    pub path: PathBuf,
+    pub status: SyncStatus,
+    pub diff: FileDiff,
+    /// 旧内容在回收站中的条目
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trash_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// 选出要同步的同一 skill 的各份副本，链接与其指向的目录只算一份
+///
+/// `name` 可以是名称或 `分类/名称`；不同分类下的同名 skill 是不同的 skill，
+/// 只写名称却匹配到多个分类时返回各候选的带分类名称。
+pub fn select_copies<'a>(skills: &'a [Skill], name: &str) -> Result<Vec<&'a Skill>, Vec<String>> {
+    let copies = skill::dedupe_by_real_path(
+        skills
+            .iter()
+            .filter(|skill| skill.name == name || skill.qualified_name() == name),
+    );
+    let qualified_names: BTreeSet<String> = copies.iter().map(|skill| skill.qualified_name()).collect();
+    if qualified_names.len() > 1 {
+        return Err(qualified_names.into_iter().collect());
+    }
+    Ok(copies)
+}
+
+/// 选出最近修改过的副本作为同步源
+pub fn newest_copy<'a>(copies: &[&'a Skill]) -> Option<&'a Skill> {
+    copies
+        .iter()
+        .copied()
+        .max_by_key(|skill| content::last_modified(&skill.path).ok())
+}
+
+/// 生成同步计划：源以外的每份副本与源逐文件比较；分类与源不同的副本是另一个 skill，不参与同步
+pub fn plan_sync(source_skill: &Skill, copies: &[&Skill]) -> io::Result<Vec<SyncOutcome>> {
+    let source_real_path = real_path(&source_skill.path);
+    let source_qualified_name = source_skill.qualified_name();
+    let source_file_hashes = content::hash_files(&source_skill.path)?;
+    Ok(copies
+        .iter()
+        .filter(|skill| skill.qualified_name() == source_qualified_name)
+        .filter(|skill| real_path(&skill.path) != source_real_path)
+        .map(|skill| {
+            let mut outcome = SyncOutcome {
+                name: skill.name.clone(),
+                tool: skill.tool.clone(),
+                path: skill.path.clone(),
+                status: SyncStatus::Planned,
+                diff: FileDiff::default(),
+                trash_id: None,
+                message: None,
+            };
+            match content::hash_files(&skill.path) {
+                Ok(file_hashes) => {
+                    outcome.diff = FileDiff::between(&file_hashes, &source_file_hashes);
+                    if outcome.diff.is_empty() {
+                        outcome.status = SyncStatus::UpToDate;
+                    }
+                }
+                Err(e) => {
+                    outcome.status = SyncStatus::Failed;
+                    outcome.message = Some(format!("无法读取: {}", e));
+                }
+            }
+            outcome
+        })
+        .collect())
+}
+
+/// 执行计划中待同步的副本：先复制源到临时目录，再将旧内容交给删除方式处理，最后替换到位
+///
+/// 副本是链接时写入其指向的目录，链接本身保持不变。
+pub fn apply_sync(source_skill: &Skill, planned_outcomes: Vec<SyncOutcome>, delete_mode: &DeleteMode) -> Vec<SyncOutcome> {
+    planned_outcomes
+        .into_iter()
+        .map(|mut outcome| {
+            if outcome.status != SyncStatus::Planned {
+                return outcome;
+            }
+            match replace_with_copy(source_skill, &outcome, delete_mode) {
+                Ok(trash_id) => {
+                    outcome.status = SyncStatus::Synced;
+                    outcome.trash_id = trash_id;
+                }
+                Err(message) => {
+                    outcome.status = SyncStatus::Failed;
+                    outcome.message = Some(message);
+                }
+            }
+            outcome
+        })
+        .collect()
+}
+
+fn replace_with_copy(source_skill: &Skill, outcome: &SyncOutcome, delete_mode: &DeleteMode) -> Result<Option<String>, String> {
+    let destination_path = real_path(&outcome.path);
+    let mut temporary_file_name = destination_path.file_name().unwrap_or_default().to_os_string();
+    temporary_file_name.push(".sync-tmp");
+    let temporary_path = destination_path.with_file_name(temporary_file_name);
+
+    if let Err(e) = fs_util::copy_dir_all(&source_skill.path, &temporary_path) {
+        let _ = fs::remove_dir_all(&temporary_path);
+        return Err(format!("复制失败: {}", e));
+    }
+    let trash_entry = match delete_mode.delete(&destination_path, &outcome.name, &outcome.tool) {
+        Ok(trash_entry) => trash_entry,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&temporary_path);
+            return Err(e.to_string());
+        }
+    };
+    let trash_id = trash_entry.map(|trash_entry| trash_entry.id);
+    fs::rename(&temporary_path, &destination_path).map_err(|e| match &trash_id {
+        Some(trash_id) => format!("替换失败（旧内容在回收站 {}）: {}", trash_id, e),
+        None => format!("替换失败: {}", e),
+    })?;
+    Ok(trash_id)
+}
+
+fn real_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+    use crate::artifact::ArtifactKind;
+    use crate::scanner::{RootKind, ScanRoot};
+
+    fn write_skill(root_path: &Path, tool_name: &str, skill_relative_path: &str, files: &[(&str, &str)]) -> Skill {
+        let skill_path = root_path.join(skill_relative_path);
+        for (relative_path, content) in files {
+            let file_path = skill_path.join(relative_path);
+            fs::create_dir_all(file_path.parent().unwrap()).expect("failed to create parent directory");
+            fs::write(file_path, content).expect("failed to write file");
+        }
+        Skill::from_path(
+            skill_path,
+            &ScanRoot {
+                tool_name: tool_name.to_string(),
+                path: root_path.to_path_buf(),
+                kind: RootKind::Custom,
+                max_depth: None,
+                artifact_kind: ArtifactKind::Skill,
+            },
+        )
+        .expect("skill should load")
+    }
+
+    #[test]
+    fn plan_then_apply_propagates_source_and_trashes_old_content() {
+        let temp_directory_path = create_temp_directory("sync");
+        let source_skill = write_skill(
+            &temp_directory_path.join("claude"),
+            "Claude Code",
+            "pdf",
+            &[("SKILL.md", "# pdf v2"), ("scripts/run.sh", "echo 2")],
+        );
+        let stale_skill = write_skill(
+            &temp_directory_path.join("cursor"),
+            "Cursor",
+            "pdf",
+            &[("SKILL.md", "# pdf v1"), ("notes.md", "old")],
+        );
+        let current_skill = write_skill(
+            &temp_directory_path.join("codex"),
+            "OpenAI Codex",
+            "pdf",
+            &[("SKILL.md", "# pdf v2"), ("scripts/run.sh", "echo 2")],
+        );
+
+        let planned_outcomes = plan_sync(&source_skill, &[&source_skill, &stale_skill, &current_skill])
+            .expect("plan should succeed");
+        assert_eq!(planned_outcomes.len(), 2);
+        assert_eq!(planned_outcomes[0].status, SyncStatus::Planned);
+        assert_eq!(planned_outcomes[0].diff.added, vec!["scripts/run.sh".to_string()]);
+        assert_eq!(planned_outcomes[0].diff.removed, vec!["notes.md".to_string()]);
+        assert_eq!(planned_outcomes[0].diff.modified, vec!["SKILL.md".to_string()]);
+        assert_eq!(planned_outcomes[1].status, SyncStatus::UpToDate);
+
+        let trash = crate::trash::Trash::new(temp_directory_path.join("trash"));
+        let outcomes = apply_sync(&source_skill, planned_outcomes, &DeleteMode::Trash(trash));
+        assert_eq!(outcomes[0].status, SyncStatus::Synced);
+        assert!(outcomes[0].trash_id.is_some());
+        assert_eq!(
+            content::hash_directory(&stale_skill.path).unwrap(),
+            content::hash_directory(&source_skill.path).unwrap()
+        );
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+
+    #[test]
+    fn copies_in_different_categories_are_not_synced() {
+        let temp_directory_path = create_temp_directory("sync-categories");
+        let claude_root_path = temp_directory_path.join("claude");
+        let cursor_root_path = temp_directory_path.join("cursor");
+        let skills = vec![
+            write_skill(&claude_root_path, "Claude Code", "frontend/utils", &[("SKILL.md", "# frontend")]),
+            write_skill(&cursor_root_path, "Cursor", "frontend/utils", &[("SKILL.md", "# frontend v0")]),
+            write_skill(&cursor_root_path, "Cursor", "backend/utils", &[("SKILL.md", "# backend")]),
+        ];
+
+        assert_eq!(
+            select_copies(&skills, "utils").unwrap_err(),
+            vec!["backend/utils".to_string(), "frontend/utils".to_string()]
+        );
+        let frontend_copies = select_copies(&skills, "frontend/utils").expect("qualified name should be unique");
+        assert_eq!(frontend_copies.len(), 2);
+
+        // 即使调用方传入了其他分类的同名副本，计划中也不包含它
+        let all_copies: Vec<&Skill> = skills.iter().collect();
+        let planned_outcomes = plan_sync(&skills[0], &all_copies).expect("plan should succeed");
+        assert_eq!(planned_outcomes.len(), 1);
+        assert_eq!(planned_outcomes[0].path, skills[1].path);
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
@@ -0,0 +1,23 @@
+//! 单元测试与集成测试共用的辅助函数
+//!
+//! 集成测试通过 `#[path = "../src/test_support.rs"] mod test_support;` 引入。
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 在系统临时目录下创建本次测试独占的目录，由调用方在测试结束时删除
+pub fn create_temp_directory(test_name: &str) -> PathBuf {
+    let timestamp_nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is earlier than UNIX_EPOCH")
+        .as_nanos();
+    let temp_directory_path = std::env::temp_dir().join(format!(
+        "skills-scanner-test-{}-{}-{}",
+        test_name,
+        std::process::id(),
+        timestamp_nanos
+    ));
+    fs::create_dir_all(&temp_directory_path).expect("failed to create temporary test directory");
+    temp_directory_path
+}
@@ -0,0 +1,449 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::fs_util;
+
+/// 回收站中每个条目的清单文件名
+const ENTRY_MANIFEST_FILE_NAME: &str = "entry.json";
+/// 条目中保存原始内容的子目录名
+const ENTRY_PAYLOAD_DIRECTORY_NAME: &str = "payload";
+
+/// 回收站中的一个条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// 条目 ID（即回收站中的目录名）
+    pub id: String,
+    /// 被删除项的名称
+    pub name: String,
+    /// 所属工具
+    pub tool: String,
+    /// 删除前的原始路径
+    pub original_path: PathBuf,
+    /// 删除时间（Unix 秒）
+    pub deleted_at: u64,
+}
+
+impl TrashEntry {
+    /// 距删除时间的时长
+    pub fn age(&self) -> Duration {
+        Duration::from_secs(unix_now().saturating_sub(self.deleted_at))
+    }
+}
+
+/// 列出或清理回收站的结果
+#[derive(Debug, Default)]
+pub struct TrashListing {
+    /// 有效的条目
+    pub entries: Vec<TrashEntry>,
+    /// 因清单损坏而跳过的条目
+    pub invalid_entries: Vec<TrashError>,
+}
+
+/// 回收站操作失败的原因
+#[derive(Debug)]
+pub enum TrashError {
+    /// 文件系统操作失败
+    Io { path: PathBuf, source: io::Error },
+    /// 条目清单损坏
+    InvalidEntry { path: PathBuf, message: String },
+    /// 找不到指定 ID 的条目
+    NotFound { id: String },
+    /// 原始路径已被占用，无法恢复
+    RestoreConflict { path: PathBuf },
+}
+
+impl fmt::Display for TrashError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrashError::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            TrashError::InvalidEntry { path, message } => {
+                write!(f, "回收站条目损坏 {}: {}", path.display(), message)
+            }
+            TrashError::NotFound { id } => write!(f, "回收站中没有 ID 为 `{}` 的条目", id),
+            TrashError::RestoreConflict { path } => {
+                write!(f, "原始位置已存在同名内容: {}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TrashError {}
+
+/// 扫描器管理的回收站，位于 `<data_local>/skills-scanner/trash/`
+///
+/// 每个条目是一个目录：`entry.json` 记录原始路径、工具与删除时间，`payload/` 保存被删除的内容。
+pub struct Trash {
+    root_path: PathBuf,
+}
+
+impl Trash {
+    /// 使用指定目录作为回收站
+    pub fn new(root_path: PathBuf) -> Self {
+        Self { root_path }
+    }
+
+    /// 打开默认位置的回收站
+    pub fn open_default() -> Option<Self> {
+        dirs::data_local_dir()
+            .map(|data_directory_path| Self::new(data_directory_path.join("skills-scanner").join("trash")))
+    }
+
+    /// 将文件或目录移入回收站
+    pub fn move_in(&self, original_path: &Path, name: &str, tool: &str) -> Result<TrashEntry, TrashError> {
+        let deleted_at = unix_now();
+        let entry_directory_path = self.allocate_entry_directory(deleted_at, name)?;
+        let entry = TrashEntry {
+            id: file_name_string(&entry_directory_path),
+            name: name.to_string(),
+            tool: tool.to_string(),
+            original_path: fs_util::absolutize(original_path),
+            deleted_at,
+        };
+
+        // 先写清单再移动内容，保证回收站中的内容总能被列出与恢复
+        if let Err(error) = write_entry_manifest(&entry_directory_path, &entry) {
+            let _ = fs::remove_dir_all(&entry_directory_path);
+            return Err(error);
+        }
+
+        let payload_path = payload_path(&entry_directory_path, &entry.original_path);
+        if let Err(source) = fs_util::move_path(original_path, &payload_path) {
+            // 内容尚未移入时原始内容保持不动，只清理空条目；已移入的内容保留在条目中
+            if fs::symlink_metadata(&payload_path).is_err() {
+                let _ = fs::remove_dir_all(&entry_directory_path);
+            }
+            return Err(TrashError::Io {
+                path: original_path.to_path_buf(),
+                source,
+            });
+        }
+        Ok(entry)
+    }
+
+    /// 列出回收站中的条目，按删除时间从新到旧排列；清单损坏的条目跳过并单独返回
+    pub fn list(&self) -> Result<TrashListing, TrashError> {
+        let read_directory = match fs::read_dir(&self.root_path) {
+            Ok(read_directory) => read_directory,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(TrashListing::default()),
+            Err(source) => {
+                return Err(TrashError::Io {
+                    path: self.root_path.clone(),
+                    source,
+                })
+            }
+        };
+
+        let mut listing = TrashListing::default();
+        for directory_entry in read_directory.flatten() {
+            let entry_directory_path = directory_entry.path();
+            if entry_directory_path.join(ENTRY_MANIFEST_FILE_NAME).is_file() {
+                match read_entry_manifest(&entry_directory_path) {
+                    Ok(entry) => listing.entries.push(entry),
+                    Err(error) => listing.invalid_entries.push(error),
+                }
+            }
+        }
+        listing
+            .entries
+            .sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(listing)
+    }
+
+    /// 将条目恢复到原始位置
+    pub fn restore(&self, id: &str) -> Result<TrashEntry, TrashError> {
+        let entry_directory_path = self.entry_directory_path(id)?;
+        let entry = read_entry_manifest(&entry_directory_path)?;
+        if entry.original_path.exists() || fs::symlink_metadata(&entry.original_path).is_ok() {
+            return Err(TrashError::RestoreConflict {
+                path: entry.original_path.clone(),
+            });
+        }
+
+        let payload_path = payload_path(&entry_directory_path, &entry.original_path);
+        fs_util::move_path(&payload_path, &entry.original_path).map_err(|source| TrashError::Io {
+            path: entry.original_path.clone(),
+            source,
+        })?;
+        fs::remove_dir_all(&entry_directory_path).map_err(|source| TrashError::Io {
+            path: entry_directory_path,
+            source,
+        })?;
+        Ok(entry)
+    }
+
+    /// 永久删除早于 `older_than` 的条目；返回的 `entries` 为被删除的条目，损坏的条目不会被删除
+    pub fn purge(&self, older_than: Duration) -> Result<TrashListing, TrashError> {
+        let listing = self.list()?;
+        let mut purged_entries = Vec::new();
+        for entry in listing.entries {
+            if entry.age() < older_than {
+                continue;
+            }
+            let entry_directory_path = self.root_path.join(&entry.id);
+            fs::remove_dir_all(&entry_directory_path).map_err(|source| TrashError::Io {
+                path: entry_directory_path,
+                source,
+            })?;
+            purged_entries.push(entry);
+        }
+        Ok(TrashListing {
+            entries: purged_entries,
+            invalid_entries: listing.invalid_entries,
+        })
+    }
+
+    /// 根据 ID 定位条目目录，拒绝包含路径分隔符的 ID
+    fn entry_directory_path(&self, id: &str) -> Result<PathBuf, TrashError> {
+        let is_plain_id = !id.is_empty() && Path::new(id).file_name().is_some_and(|file_name| file_name == id);
+        let entry_directory_path = self.root_path.join(id);
+        if !is_plain_id || !entry_directory_path.join(ENTRY_MANIFEST_FILE_NAME).is_file() {
+            return Err(TrashError::NotFound { id: id.to_string() });
+        }
+        Ok(entry_directory_path)
+    }
+
+    /// 创建新的条目目录：`<删除时间>-<名称>`，重名时追加序号
+    fn allocate_entry_directory(&self, deleted_at: u64, name: &str) -> Result<PathBuf, TrashError> {
+        fs::create_dir_all(&self.root_path).map_err(|source| TrashError::Io {
+            path: self.root_path.clone(),
+            source,
+        })?;
+
+        let base_id = format!("{}-{}", deleted_at, sanitize_id_component(name));
+        let mut sequence = 1;
+        loop {
+            let id = if sequence == 1 {
+                base_id.clone()
+            } else {
+                format!("{}-{}", base_id, sequence)
+            };
+            let entry_directory_path = self.root_path.join(&id);
+            match fs::create_dir(&entry_directory_path) {
+                Ok(()) => return Ok(entry_directory_path),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => sequence += 1,
+                Err(source) => {
+                    return Err(TrashError::Io {
+                        path: entry_directory_path,
+                        source,
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// 删除方式：默认移入回收站，显式指定时永久删除
+pub enum DeleteMode {
+    /// 移入回收站，可通过 `trash restore` 恢复
+    Trash(Trash),
+    /// 直接从磁盘删除
+    Permanent,
+}
+
+impl DeleteMode {
+    /// 删除文件或目录；移入回收站时返回对应条目
+    pub fn delete(&self, path: &Path, name: &str, tool: &str) -> Result<Option<TrashEntry>, TrashError> {
+        match self {
+            DeleteMode::Trash(trash) => trash.move_in(path, name, tool).map(Some),
+            DeleteMode::Permanent => fs_util::remove_path(path)
+                .map(|_| None)
+                .map_err(|source| TrashError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                }),
+        }
+    }
+
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, DeleteMode::Permanent)
+    }
+}
+
+/// 解析 `30d`、`12h`、`2w`、`45m` 形式的时长
+pub fn parse_age(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let unit_start = text.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = text.split_at(unit_start);
+    let amount: u64 = amount.parse().ok()?;
+    let unit_seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    amount.checked_mul(unit_seconds).map(Duration::from_secs)
+}
+
+/// 将时长格式化为简短的中文描述（如 `3 天前`）
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        0..60 => "刚刚".to_string(),
+        60..3_600 => format!("{} 分钟前", seconds / 60),
+        3_600..86_400 => format!("{} 小时前", seconds / 3_600),
+        _ => format!("{} 天前", seconds / 86_400),
+    }
+}
+
+fn payload_path(entry_directory_path: &Path, original_path: &Path) -> PathBuf {
+    let payload_name = original_path
+        .file_name()
+        .map(|file_name| file_name.to_os_string())
+        .unwrap_or_else(|| "item".into());
+    entry_directory_path
+        .join(ENTRY_PAYLOAD_DIRECTORY_NAME)
+        .join(payload_name)
+}
+
+fn write_entry_manifest(entry_directory_path: &Path, entry: &TrashEntry) -> Result<(), TrashError> {
+    let manifest_path = entry_directory_path.join(ENTRY_MANIFEST_FILE_NAME);
+    let content = serde_json::to_string_pretty(entry).map_err(|e| TrashError::InvalidEntry {
+        path: manifest_path.clone(),
+        message: e.to_string(),
+    })?;
+    fs::write(&manifest_path, content).map_err(|source| TrashError::Io {
+        path: manifest_path,
+        source,
+    })
+}
+
+fn read_entry_manifest(entry_directory_path: &Path) -> Result<TrashEntry, TrashError> {
+    let manifest_path = entry_directory_path.join(ENTRY_MANIFEST_FILE_NAME);
+    let content = fs::read_to_string(&manifest_path).map_err(|source| TrashError::Io {
+        path: manifest_path.clone(),
+        source,
+    })?;
+    let mut entry: TrashEntry = serde_json::from_str(&content).map_err(|e| TrashError::InvalidEntry {
+        path: manifest_path,
+        message: e.to_string(),
+    })?;
+    // 目录名是条目 ID 的唯一来源
+    entry.id = file_name_string(entry_directory_path);
+    Ok(entry)
+}
+
+/// ID 中只保留字母数字、`-`、`_` 与 `.`，其余字符替换为 `_`
+fn sanitize_id_component(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.is_empty() {
+        "item".to_string()
+    } else {
+        sanitized
+    }
+}
+
+fn file_name_string(path: &Path) -> String {
+    path.file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+
+    #[test]
+    fn move_in_and_restore_round_trip() {
+        let temp_directory_path = create_temp_directory("trash-round-trip");
+        let skill_directory_path = temp_directory_path.join("skills").join("my-skill");
+        fs::create_dir_all(skill_directory_path.join("scripts")).expect("failed to create skill directory");
+        fs::write(skill_directory_path.join("SKILL.md"), "# my skill").expect("failed to write SKILL.md");
+
+        let trash = Trash::new(temp_directory_path.join("trash"));
+        let first_entry = trash
+            .move_in(&skill_directory_path, "my-skill", "Claude Code")
+            .expect("failed to move skill into trash");
+        assert!(!skill_directory_path.exists());
+
+        // 同一秒内再次删除同名 skill 时分配不同的 ID
+        fs::create_dir_all(&skill_directory_path).expect("failed to recreate skill directory");
+        let second_entry = trash
+            .move_in(&skill_directory_path, "my-skill", "Claude Code")
+            .expect("failed to move second skill into trash");
+        assert_ne!(first_entry.id, second_entry.id);
+        assert_eq!(trash.list().expect("failed to list trash").entries.len(), 2);
+
+        trash.restore(&first_entry.id).expect("failed to restore skill");
+        assert_eq!(
+            fs::read_to_string(skill_directory_path.join("SKILL.md")).expect("restored SKILL.md missing"),
+            "# my skill"
+        );
+        assert!(matches!(
+            trash.restore(&second_entry.id),
+            Err(TrashError::RestoreConflict { .. })
+        ));
+        assert!(matches!(trash.restore("../escape"), Err(TrashError::NotFound { .. })));
+
+        assert_eq!(trash.purge(Duration::from_secs(3_600)).expect("failed to purge").entries.len(), 0);
+        assert_eq!(trash.purge(Duration::ZERO).expect("failed to purge").entries.len(), 1);
+        assert!(trash.list().expect("failed to list trash").entries.is_empty());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+
+    #[test]
+    fn failed_move_keeps_original_and_corrupt_entries_are_skipped() {
+        let temp_directory_path = create_temp_directory("trash-corrupt");
+        let trash = Trash::new(temp_directory_path.join("trash"));
+
+        let missing_skill_path = temp_directory_path.join("skills").join("missing");
+        assert!(matches!(
+            trash.move_in(&missing_skill_path, "missing", "Claude Code"),
+            Err(TrashError::Io { .. })
+        ));
+        assert!(trash.list().expect("failed to list trash").entries.is_empty());
+
+        let skill_directory_path = temp_directory_path.join("skills").join("my-skill");
+        fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+        let valid_entry = trash
+            .move_in(&skill_directory_path, "my-skill", "Claude Code")
+            .expect("failed to move skill into trash");
+        let corrupt_entry_path = temp_directory_path.join("trash").join("0-corrupt");
+        fs::create_dir_all(&corrupt_entry_path).expect("failed to create corrupt entry");
+        fs::write(corrupt_entry_path.join(ENTRY_MANIFEST_FILE_NAME), "{").expect("failed to write entry.json");
+
+        let listing = trash.list().expect("corrupt entries should not fail the listing");
+        assert_eq!(listing.entries.len(), 1);
+        assert_eq!(listing.entries[0].id, valid_entry.id);
+        assert!(matches!(listing.invalid_entries.as_slice(), [TrashError::InvalidEntry { .. }]));
+
+        let purge_listing = trash.purge(Duration::ZERO).expect("corrupt entries should not fail purge");
+        assert_eq!(purge_listing.entries.len(), 1);
+        assert_eq!(purge_listing.invalid_entries.len(), 1);
+        assert!(corrupt_entry_path.exists());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+
+    #[test]
+    fn parse_age_accepts_supported_units() {
+        assert_eq!(parse_age("30d"), Some(Duration::from_secs(30 * 86_400)));
+        assert_eq!(parse_age("12h"), Some(Duration::from_secs(12 * 3_600)));
+        assert_eq!(parse_age("2w"), Some(Duration::from_secs(14 * 86_400)));
+        assert_eq!(parse_age("30"), None);
+        assert_eq!(parse_age("d"), None);
+        assert_eq!(parse_age("5y"), None);
+    }
+}
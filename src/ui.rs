@@ -1,25 +1,68 @@
-use std::fs;
-use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::collections::HashSet;
 use console::{style, Key, Term};
 use dialoguer::{MultiSelect, Confirm, Select, theme::ColorfulTheme};
 
-type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+/// 交互界面的错误
+#[derive(Debug)]
+pub enum UiError {
+    /// 终端读写失败（包括提示组件的输入输出错误）
+    Terminal(io::Error),
+}
+
+impl fmt::Display for UiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UiError::Terminal(source) => write!(f, "终端读写失败: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for UiError {}
+
+impl From<io::Error> for UiError {
+    fn from(source: io::Error) -> Self {
+        UiError::Terminal(source)
+    }
+}
 
-use crate::skill::Skill;
+impl From<dialoguer::Error> for UiError {
+    fn from(source: dialoguer::Error) -> Self {
+        match source {
+            dialoguer::Error::IO(source) => UiError::Terminal(source),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, UiError>;
+
+use skills_scanner::artifact::{ArtifactKind, ClaudeExtension};
+use skills_scanner::content::FileDiff;
+use skills_scanner::delete::{self, DeleteOutcome, DeleteStatus};
+use skills_scanner::dupes::{DupeReport, SkillCopy};
+use skills_scanner::finding::{Finding, Severity};
+use skills_scanner::install::{self, ConflictPolicy, InstallMethod, InstallOutcome, InstallScope, InstallStatus};
+use skills_scanner::link::{RepairOutcome, RepairStatus, UnlinkOutcome, UnlinkStatus};
+use skills_scanner::mcp::McpConfigFile;
+use skills_scanner::registry::{EntrySource, ToolEntry, ToolRegistry};
+use skills_scanner::resolve::ToolResolution;
+use skills_scanner::skill::{self, Skill};
+use skills_scanner::sync::{SyncOutcome, SyncStatus};
+use skills_scanner::trash::{self, DeleteMode, TrashEntry};
+use skills_scanner::watch::{WatchEvent, WatchEventKind};
 
 pub enum MainMenuAction {
     BrowseAll,
+    Install,
     Exit,
 }
 
-struct SkillGroup {
-    name: String,
-    indices: Vec<usize>,
-}
-
 const MAX_DESCRIPTION_CHAR_COUNT: usize = 40;
 const TRUNCATED_DESCRIPTION_CHAR_COUNT: usize = 37;
 const DESCRIPTION_ELLIPSIS: &str = "...";
+const _: () = assert!(TRUNCATED_DESCRIPTION_CHAR_COUNT < MAX_DESCRIPTION_CHAR_COUNT);
 
 /// 确保光标恢复显示的 RAII guard
 struct CursorGuard<'a>(&'a Term);
@@ -38,7 +81,7 @@ pub fn show_main_menu(skill_count: usize) -> Result<MainMenuAction> {
         style(skill_count).green().bold()
     );
 
-    let items = vec!["浏览所有 skills", "退出"];
+    let items = vec!["浏览所有 skills、命令与子代理", "安装 skill 到其他工具", "退出"];
 
     let selection = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("请选择操作")
@@ -48,25 +91,12 @@ pub fn show_main_menu(skill_count: usize) -> Result<MainMenuAction> {
 
     match selection {
         0 => Ok(MainMenuAction::BrowseAll),
+        1 => Ok(MainMenuAction::Install),
         _ => Ok(MainMenuAction::Exit),
     }
 }
 
-/// 按名称分组 skills
-fn group_skills(skills: &[Skill], indices: &[usize]) -> Vec<SkillGroup> {
-    let mut map: BTreeMap<String, Vec<usize>> = BTreeMap::new();
-    for &idx in indices {
-        let name = skills[idx].name.clone();
-        map.entry(name).or_default().push(idx);
-    }
-    map.into_iter()
-        .map(|(name, indices)| SkillGroup { name, indices })
-        .collect()
-}
-
 fn truncate_description(description: &str) -> String {
-    debug_assert!(TRUNCATED_DESCRIPTION_CHAR_COUNT < MAX_DESCRIPTION_CHAR_COUNT);
-
     let mut truncated_end_index = description.len();
     let mut character_count = 0;
 
@@ -90,13 +120,17 @@ fn truncate_description(description: &str) -> String {
 
 /// 交互式选择并删除 skills（完整流程）
 /// 返回 Ok(true) 表示有 skills 被删除，需要重新扫描
-pub fn interactive_select_and_delete(skills: &[Skill], indices: &[usize]) -> Result<bool> {
+pub fn interactive_select_and_delete(
+    skills: &[Skill],
+    indices: &[usize],
+    delete_mode: &DeleteMode,
+) -> Result<bool> {
     if indices.is_empty() {
         println!("{}", style("未找到任何 skills").yellow());
         return Ok(false);
     }
 
-    let groups = group_skills(skills, indices);
+    let groups = skill::group_skills(skills, indices);
 
     let term = Term::stdout();
 
@@ -114,20 +148,42 @@ pub fn interactive_select_and_delete(skills: &[Skill], indices: &[usize]) -> Res
                 format!(
                     "{:<12} > {:<20} {}",
                     tools[0],
-                    g.name,
+                    skill.qualified_name(),
                     style(truncated_desc).dim()
                 )
             } else {
+                let distinct_hashes: HashSet<Option<&str>> = g
+                    .indices
+                    .iter()
+                    .map(|&i| skills[i].content_hash.as_deref())
+                    .collect();
+                let content_label = if distinct_hashes.len() == 1 {
+                    style("内容一致").green()
+                } else {
+                    style("内容已分叉").yellow()
+                };
                 format!(
-                    "{:<35} {}",
+                    "{:<35} {} {}",
                     g.name,
-                    style(format!("{}个来源: {}", tools.len(), tools.join(", "))).dim()
+                    style(format!("{}个来源: {}", tools.len(), tools.join(", "))).dim(),
+                    content_label
                 )
             }
         })
         .collect();
 
-    let search_keys: Vec<String> = groups.iter().map(|g| g.name.clone()).collect();
+    // 分类路径也参与搜索
+    let search_keys: Vec<String> = groups
+        .iter()
+        .map(|g| {
+            let mut search_key = g.name.clone();
+            for category in g.indices.iter().filter_map(|&i| skills[i].category.as_deref()) {
+                search_key.push(' ');
+                search_key.push_str(category);
+            }
+            search_key
+        })
+        .collect();
 
     // 构建静态头部信息
     let header_lines = vec![
@@ -192,7 +248,7 @@ pub fn interactive_select_and_delete(skills: &[Skill], indices: &[usize]) -> Res
                 sub_items.push(format!(
                     "{:<12} > {:<20} {}",
                     skill.tool,
-                    skill.name,
+                    skill.qualified_name(),
                     style(truncated_desc).dim()
                 ));
             }
@@ -222,8 +278,8 @@ pub fn interactive_select_and_delete(skills: &[Skill], indices: &[usize]) -> Res
     // 确认并删除
     let selected_skills: Vec<&Skill> = final_skill_indices.iter().map(|&i| &skills[i]).collect();
 
-    if confirm_delete(&selected_skills)? {
-        delete_skills(&selected_skills)?;
+    if confirm_delete(&selected_skills, delete_mode)? {
+        delete_skills(&selected_skills, delete_mode)?;
         show_complete_message();
         Ok(true)
     } else {
@@ -232,6 +288,115 @@ pub fn interactive_select_and_delete(skills: &[Skill], indices: &[usize]) -> Res
     }
 }
 
+/// 交互式安装 skill 到其他工具
+/// 返回 Ok(true) 表示有 skill 被安装，需要重新扫描
+pub fn interactive_install(
+    skills: &[Skill],
+    registry: &ToolRegistry,
+    delete_mode: &DeleteMode,
+) -> Result<bool> {
+    // 只有 skill 目录可以安装，命令与子代理只参与浏览和删除
+    let skills: Vec<&Skill> = skills
+        .iter()
+        .filter(|skill| skill.kind == ArtifactKind::Skill)
+        .collect();
+    if skills.is_empty() {
+        println!("{}", style("未找到任何 skills").yellow());
+        return Ok(false);
+    }
+
+    let theme = ColorfulTheme::default();
+    let skill_items: Vec<String> = skills
+        .iter()
+        .map(|skill| format!("{:<12} > {}", skill.tool, skill.qualified_name()))
+        .collect();
+    let source_index = Select::with_theme(&theme)
+        .with_prompt("选择要安装的 skill")
+        .items(&skill_items)
+        .default(0)
+        .max_length(15)
+        .interact()?;
+    let source_skill = skills[source_index];
+
+    let method_index = Select::with_theme(&theme)
+        .with_prompt("安装方式")
+        .items(&["复制", "链接（各工具共享同一份规范副本）"])
+        .default(0)
+        .interact()?;
+    let install_method = if method_index == 0 {
+        InstallMethod::Copy
+    } else {
+        InstallMethod::Link
+    };
+
+    let scope_index = Select::with_theme(&theme)
+        .with_prompt("安装范围")
+        .items(&["用户级目录", "当前项目"])
+        .default(0)
+        .interact()?;
+    let scope = if scope_index == 0 {
+        InstallScope::User
+    } else {
+        InstallScope::Project
+    };
+
+    let install_targets: Vec<_> = install::install_targets(registry, scope)
+        .into_iter()
+        .filter(|install_target| install_target.skills_directory_path != source_skill.root)
+        .collect();
+    if install_targets.is_empty() {
+        println!("\n{}\n", style("没有可安装的目标工具").yellow());
+        return Ok(false);
+    }
+    let target_items: Vec<String> = install_targets
+        .iter()
+        .map(|install_target| {
+            format!(
+                "{:<28} {}",
+                install_target.tool_name,
+                style(install_target.skills_directory_path.display()).dim()
+            )
+        })
+        .collect();
+    let target_indices = MultiSelect::with_theme(&theme)
+        .with_prompt("选择目标工具（空格选择，Enter 确认）")
+        .items(&target_items)
+        .interact()?;
+    if target_indices.is_empty() {
+        println!("\n未选择任何目标工具\n");
+        return Ok(false);
+    }
+
+    let conflict_index = Select::with_theme(&theme)
+        .with_prompt("目标位置已存在同名 skill 时")
+        .items(&["跳过", "覆盖（原有内容移入回收站）", "以新名称安装"])
+        .default(0)
+        .interact()?;
+    let conflict_policy = match conflict_index {
+        0 => ConflictPolicy::Skip,
+        1 => ConflictPolicy::Overwrite,
+        _ => ConflictPolicy::Rename,
+    };
+
+    let install_outcomes: Vec<InstallOutcome> = target_indices
+        .iter()
+        .map(|&target_index| {
+            install::install_skill(
+                source_skill,
+                &install_targets[target_index],
+                install_method,
+                conflict_policy,
+                delete_mode,
+            )
+        })
+        .collect();
+    display_install_outcomes(source_skill, &install_outcomes);
+
+    Ok(install_outcomes.iter().any(|install_outcome| {
+        !matches!(install_outcome.status, InstallStatus::Skipped | InstallStatus::Failed)
+    }))
+}
+
 /// 带实时搜索的多选组件
 fn searchable_multi_select(
     term: &Term,
@@ -248,8 +413,7 @@ fn searchable_multi_select(
     let (term_height, _) = term.size();
     let page_size = (term_height as usize)
         .saturating_sub(header.len() + 8)
-        .max(5)
-        .min(20);
+        .clamp(5, 20);
 
     term.hide_cursor()?;
     let _guard = CursorGuard(term);
@@ -307,8 +471,7 @@ fn searchable_multi_select(
                 ))?;
             }
 
-            for vi in start..end {
-                let idx = filtered[vi];
+            for (vi, &idx) in filtered.iter().enumerate().take(end).skip(start) {
                 let is_cur = vi == cursor;
                 let is_sel = selected.contains(&idx);
 
@@ -355,14 +518,13 @@ fn searchable_multi_select(
 
         // 读取按键
         match term.read_key()? {
-            Key::Char(' ') => {
-                if !filtered.is_empty() {
-                    let idx = filtered[cursor];
-                    if !selected.remove(&idx) {
-                        selected.insert(idx);
-                    }
+            Key::Char(' ') if !filtered.is_empty() => {
+                let idx = filtered[cursor];
+                if !selected.remove(&idx) {
+                    selected.insert(idx);
                 }
             }
+            Key::Char(' ') => {}
             Key::Char(c) if !c.is_control() => {
                 search.push(c);
                 apply_filter(&search, search_keys, &mut filtered, &mut cursor);
@@ -379,14 +541,10 @@ fn searchable_multi_select(
                 apply_filter(&search, search_keys, &mut filtered, &mut cursor);
             }
             Key::ArrowUp => {
-                if cursor > 0 {
-                    cursor -= 1;
-                }
+                cursor = cursor.saturating_sub(1);
             }
-            Key::ArrowDown => {
-                if !filtered.is_empty() && cursor < filtered.len() - 1 {
-                    cursor += 1;
-                }
+            Key::ArrowDown if !filtered.is_empty() && cursor < filtered.len() - 1 => {
+                cursor += 1;
             }
             Key::Enter => {
                 let mut result: Vec<usize> = selected.into_iter().collect();
@@ -436,30 +594,156 @@ pub fn display_skills(skills: &[Skill]) {
         style(skills.len()).green().bold()
     );
 
-    for skill in skills {
+    // 有分类目录时，在分类变化处显示分类标题；配合 --sort category 可按分类分组
+    let has_categories = skills.iter().any(|skill| skill.category.is_some());
+    let mut current_category = None;
+    for (skill_index, skill) in skills.iter().enumerate() {
+        if has_categories && (skill_index == 0 || skill.category.as_deref() != current_category) {
+            current_category = skill.category.as_deref();
+            println!(
+                "{} {}\n",
+                style("📁").cyan(),
+                style(current_category.unwrap_or("（无分类）")).cyan().bold()
+            );
+        }
         println!(
-            "  {} {}  {}",
+            "  {:<20} {} {}  {}",
+            style(skill.kind.label()).magenta(),
             style(&skill.tool).cyan().bold(),
             style(">").dim(),
-            style(&skill.name).white()
+            style(skill.qualified_name()).white()
         );
         if let Some(desc) = &skill.description {
             println!("     {}", style(desc).dim());
         }
+        if let Some(rule_activation) = &skill.rule {
+            println!("     {}", style(rule_activation.summary()).yellow());
+        }
+        if let Some(extension_summary) = skill.extension.as_ref().and_then(ClaudeExtension::summary) {
+            println!("     {}", style(extension_summary).yellow());
+        }
         println!("     {}", style(skill.path.display()).dim().italic());
+        if let Some(link_target_path) = &skill.link_target {
+            println!("     {}", style(format!("↪ 链接到 {}", link_target_path.display())).cyan());
+        }
         println!();
     }
 }
 
+/// 显示问题列表（lint/audit 等命令的文本输出）
+pub fn display_findings(findings: &[Finding]) {
+    if findings.is_empty() {
+        println!("\n{} 未发现问题\n", style("✓").green());
+        return;
+    }
+
+    println!();
+    for finding in findings {
+        let location = match (finding.line, finding.column) {
+            (Some(line), Some(column)) => format!("{}:{}:{}", finding.path.display(), line, column),
+            (Some(line), None) => format!("{}:{}", finding.path.display(), line),
+            _ => finding.path.display().to_string(),
+        };
+        let severity_label = match finding.severity {
+            Severity::Error => style(finding.severity.label()).red().bold(),
+            Severity::Warning => style(finding.severity.label()).yellow().bold(),
+            Severity::Note => style(finding.severity.label()).cyan(),
+        };
+        println!(
+            "{}: {}[{}] {}",
+            style(location).white().bold(),
+            severity_label,
+            style(finding.rule_id).dim(),
+            finding.message
+        );
+    }
+
+    let error_count = findings.iter().filter(|finding| finding.severity == Severity::Error).count();
+    let warning_count = findings.iter().filter(|finding| finding.severity == Severity::Warning).count();
+    println!(
+        "\n共 {} 个问题: {} 个错误, {} 个警告\n",
+        style(findings.len()).bold(),
+        style(error_count).red().bold(),
+        style(warning_count).yellow().bold()
+    );
+}
+
+/// 显示工具注册表
+pub fn display_tools(tool_entries: &[ToolEntry], user_registry_path: Option<&Path>) {
+    match user_registry_path {
+        Some(path) => println!(
+            "\n{} 用户注册表: {}\n",
+            style("⚙").cyan(),
+            style(path.display()).dim()
+        ),
+        None => println!(
+            "\n{} 未找到用户注册表，仅使用内置默认值\n",
+            style("⚙").cyan()
+        ),
+    }
+
+    for tool_entry in tool_entries {
+        let name_label = if tool_entry.enabled {
+            style(&tool_entry.name).white().bold()
+        } else {
+            style(&tool_entry.name).dim().strikethrough()
+        };
+        let source_label = match tool_entry.source {
+            EntrySource::Builtin => style("").dim(),
+            EntrySource::User => style(" (user)").yellow(),
+        };
+        let depth_label = match tool_entry.max_depth {
+            Some(max_depth) => style(format!(" 深度 {}", max_depth)).cyan(),
+            None => style(String::new()),
+        };
+        let kind_label = match tool_entry.kind {
+            ArtifactKind::Skill => style(String::new()),
+            artifact_kind => style(format!(" [{}]", artifact_kind.label())).magenta(),
+        };
+        println!(
+            "  {:<36} {:<10} {}{}{}{}",
+            name_label,
+            style(tool_entry.root.label()).cyan(),
+            style(&tool_entry.path).dim(),
+            kind_label,
+            depth_label,
+            source_label
+        );
+    }
+    println!();
+}
+
 /// 确认删除对话框
-fn confirm_delete(skills: &[&Skill]) -> Result<bool> {
+fn confirm_delete(skills: &[&Skill], delete_mode: &DeleteMode) -> Result<bool> {
     if skills.is_empty() {
         return Ok(false);
     }
 
+    display_delete_plan(skills, delete_mode);
+    confirm("确认删除")
+}
+
+/// 是/否确认提示，默认为否
+pub fn confirm(prompt: &str) -> Result<bool> {
+    let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?;
+
+    Ok(confirmed)
+}
+
+/// 显示将要删除的 skills
+pub fn display_delete_plan(skills: &[&Skill], delete_mode: &DeleteMode) {
+    let action_label = if delete_mode.is_permanent() {
+        style("永久删除").red().bold()
+    } else {
+        style("移入回收站").yellow().bold()
+    };
     println!(
-        "\n{} 确定要删除以下 {} 个 skills 吗?\n",
+        "\n{} 将要{}以下 {} 个 skills:\n",
         style("⚠️").yellow(),
+        action_label,
         style(skills.len()).red().bold()
     );
 
@@ -474,40 +758,256 @@ fn confirm_delete(skills: &[&Skill]) -> Result<bool> {
     }
 
     println!();
+}
 
-    let confirmed = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("确认删除")
-        .default(false)
-        .interact()?;
+/// 执行删除操作（默认移入回收站）
+fn delete_skills(skills: &[&Skill], delete_mode: &DeleteMode) -> Result<()> {
+    let delete_outcomes = delete::delete_selected(skills, delete_mode, false);
+    display_delete_outcomes(&delete_outcomes);
+    Ok(())
+}
 
-    Ok(confirmed)
+/// 显示删除结果
+pub fn display_delete_outcomes(delete_outcomes: &[DeleteOutcome]) {
+    for delete_outcome in delete_outcomes {
+        let skill_label = format!(
+            "{} > {}",
+            style(&delete_outcome.tool).cyan(),
+            style(&delete_outcome.name).white()
+        );
+        match delete_outcome.status {
+            DeleteStatus::Planned => println!("{} 将删除: {}", style("•").yellow(), skill_label),
+            DeleteStatus::Trashed => println!(
+                "{} 已移入回收站: {}  {}",
+                style("✓").green(),
+                skill_label,
+                style(format!("(ID: {})", delete_outcome.trash_id.as_deref().unwrap_or_default())).dim()
+            ),
+            DeleteStatus::Deleted => println!("{} 已永久删除: {}", style("✓").green(), skill_label),
+            DeleteStatus::Failed => println!(
+                "{} 删除失败: {} - {}",
+                style("✗").red(),
+                skill_label,
+                style(delete_outcome.error.as_deref().unwrap_or_default()).red()
+            ),
+        }
+    }
+
+    if delete_outcomes
+        .iter()
+        .any(|delete_outcome| delete_outcome.status == DeleteStatus::Trashed)
+    {
+        println!(
+            "\n{} 可使用 {} 恢复",
+            style("↩").cyan(),
+            style("skills-scanner trash restore <ID>").cyan().bold()
+        );
+    }
 }
 
-/// 执行删除操作
-fn delete_skills(skills: &[&Skill]) -> Result<()> {
-    for skill in skills {
-        match fs::remove_dir_all(&skill.path) {
-            Ok(_) => {
-                println!(
-                    "{} 已删除: {} > {}",
-                    style("✓").green(),
-                    style(&skill.tool).cyan(),
-                    style(&skill.name).white()
-                );
+/// 显示安装结果
+pub fn display_install_outcomes(source_skill: &Skill, install_outcomes: &[InstallOutcome]) {
+    println!(
+        "\n{} 安装 {} > {}\n   {}\n",
+        style("📦").cyan(),
+        style(&source_skill.tool).cyan(),
+        style(&source_skill.name).white().bold(),
+        style(source_skill.path.display()).dim()
+    );
+
+    for install_outcome in install_outcomes {
+        let destination_label = style(install_outcome.destination.display()).dim();
+        let tool_label = style(&install_outcome.tool).cyan();
+        match install_outcome.status {
+            InstallStatus::Installed => {
+                println!("{} 已安装到 {}: {}", style("✓").green(), tool_label, destination_label)
             }
-            Err(e) => {
-                println!(
-                    "{} 删除失败: {} > {} - {}",
-                    style("✗").red(),
-                    style(&skill.tool).cyan(),
-                    style(&skill.name).white(),
-                    style(e).red()
-                );
+            InstallStatus::Replaced => println!(
+                "{} 已覆盖 {}: {}{}",
+                style("✓").green(),
+                tool_label,
+                destination_label,
+                match &install_outcome.trash_id {
+                    Some(trash_id) => format!("  {}", style(format!("(原内容 ID: {})", trash_id)).dim()),
+                    None => String::new(),
+                }
+            ),
+            InstallStatus::Linked => println!(
+                "{} 已链接到 {}: {}  {}",
+                style("✓").green(),
+                tool_label,
+                destination_label,
+                style(format!(
+                    "→ {}",
+                    install_outcome.link_target.as_deref().unwrap_or(&source_skill.path).display()
+                ))
+                .dim()
+            ),
+            InstallStatus::Renamed => {
+                println!("{} 已以新名称安装到 {}: {}", style("✓").green(), tool_label, destination_label)
             }
+            InstallStatus::Skipped => println!(
+                "{} 已跳过 {}: {}",
+                style("-").yellow(),
+                tool_label,
+                install_outcome.message.as_deref().unwrap_or_default()
+            ),
+            InstallStatus::Failed => println!(
+                "{} 安装到 {} 失败: {}",
+                style("✗").red(),
+                tool_label,
+                style(install_outcome.message.as_deref().unwrap_or_default()).red()
+            ),
         }
     }
+    println!();
+}
 
-    Ok(())
+/// 显示失效链接的修复结果
+pub fn display_repair_outcomes(repair_outcomes: &[RepairOutcome]) {
+    if repair_outcomes.is_empty() {
+        println!("\n{} 未发现失效链接\n", style("✓").green());
+        return;
+    }
+
+    println!(
+        "\n{} 发现 {} 个失效链接:\n",
+        style("🔗").cyan(),
+        style(repair_outcomes.len()).yellow().bold()
+    );
+    for repair_outcome in repair_outcomes {
+        let broken_link = &repair_outcome.broken_link;
+        println!(
+            "  {} {} {}",
+            style(&broken_link.tool).cyan(),
+            style(">").dim(),
+            style(&broken_link.name).white().bold()
+        );
+        println!(
+            "     {} {}",
+            style(broken_link.path.display()).dim(),
+            style(format!("→ {}", broken_link.link_target.display())).red()
+        );
+        let new_target_label = repair_outcome
+            .new_target
+            .as_ref()
+            .map(|new_target_path| new_target_path.display().to_string())
+            .unwrap_or_default();
+        let message = repair_outcome.message.as_deref().unwrap_or_default();
+        match repair_outcome.status {
+            RepairStatus::Planned => println!("     {} 将重新指向 {}", style("•").yellow(), new_target_label),
+            RepairStatus::Relinked => println!("     {} 已重新指向 {}", style("✓").green(), new_target_label),
+            RepairStatus::Pruned => println!("     {} 已删除失效链接", style("✓").green()),
+            RepairStatus::Unresolved => println!("     {} 无法修复: {}", style("-").yellow(), message),
+            RepairStatus::Failed => println!("     {} 修复失败: {}", style("✗").red(), style(message).red()),
+        }
+    }
+    println!();
+}
+
+/// 显示断开链接的结果
+pub fn display_unlink_outcomes(unlink_outcomes: &[UnlinkOutcome]) {
+    for unlink_outcome in unlink_outcomes {
+        let skill_label = format!(
+            "{} > {}",
+            style(&unlink_outcome.tool).cyan(),
+            style(&unlink_outcome.name).white()
+        );
+        let message = unlink_outcome.message.as_deref().unwrap_or_default();
+        match unlink_outcome.status {
+            UnlinkStatus::Planned => println!(
+                "{} 将删除链接: {}  {}",
+                style("•").yellow(),
+                skill_label,
+                style(format!("→ {}", unlink_outcome.link_target.display())).dim()
+            ),
+            UnlinkStatus::Removed => println!("{} 已删除链接: {}", style("✓").green(), skill_label),
+            UnlinkStatus::Materialized => println!("{} 已替换为独立副本: {}", style("✓").green(), skill_label),
+            UnlinkStatus::Failed => {
+                println!("{} 处理失败: {} - {}", style("✗").red(), skill_label, style(message).red())
+            }
+        }
+    }
+}
+
+/// 显示回收站条目
+pub fn display_trash_entries(trash_entries: &[TrashEntry]) {
+    if trash_entries.is_empty() {
+        println!("\n{}\n", style("回收站为空").yellow());
+        return;
+    }
+
+    println!(
+        "\n{} 回收站中有 {} 个条目:\n",
+        style("🗑").cyan(),
+        style(trash_entries.len()).green().bold()
+    );
+    for trash_entry in trash_entries {
+        println!(
+            "  {}  {} {} {}  {}",
+            style(&trash_entry.id).white().bold(),
+            style(&trash_entry.tool).cyan(),
+            style(">").dim(),
+            style(&trash_entry.name).white(),
+            style(trash::format_age(trash_entry.age())).dim()
+        );
+        println!("     {}", style(trash_entry.original_path.display()).dim().italic());
+    }
+    println!();
+}
+
+/// 按工具显示 MCP 配置文件及其中的服务器
+pub fn display_mcp_config_files(config_files: &[McpConfigFile]) {
+    if config_files.is_empty() {
+        println!("\n{}\n", style("未找到任何 MCP 配置文件").yellow());
+        return;
+    }
+
+    let server_count: usize = config_files.iter().map(|config_file| config_file.servers.len()).sum();
+    println!(
+        "\n{} 在 {} 个配置文件中找到 {} 个 MCP 服务器:\n",
+        style("🔌").cyan(),
+        style(config_files.len()).green(),
+        style(server_count).green().bold()
+    );
+    for config_file in config_files {
+        println!(
+            "  {}  {}",
+            style(&config_file.tool).cyan().bold(),
+            style(config_file.path.display()).dim().italic()
+        );
+        if let Some(error) = &config_file.error {
+            println!("     {} {}", style("✗").red(), style(error).red());
+        } else if config_file.servers.is_empty() {
+            println!("     {}", style("（没有服务器）").dim());
+        }
+        for server in &config_file.servers {
+            let launch_label = match (&server.command, &server.url) {
+                (Some(command), _) => std::iter::once(command.as_str())
+                    .chain(server.args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                (None, Some(url)) => url.clone(),
+                (None, None) => String::new(),
+            };
+            println!(
+                "     {} {:<20} {}  {}",
+                style("•").cyan(),
+                style(&server.name).white().bold(),
+                style(&server.transport).magenta(),
+                launch_label
+            );
+            let mut secret_keys: Vec<&str> = server.env.keys().map(String::as_str).collect();
+            secret_keys.extend(server.headers.keys().map(String::as_str));
+            if !secret_keys.is_empty() {
+                println!("       {}", style(format!("环境变量/请求头: {}", secret_keys.join(", "))).dim());
+            }
+            if let Some(project_path) = &server.project {
+                println!("       {}", style(format!("仅用于项目 {}", project_path.display())).dim());
+            }
+        }
+        println!();
+    }
 }
 
 /// 显示扫描开始信息
@@ -524,6 +1024,258 @@ fn show_complete_message() {
     println!("\n{} 操作完成!\n", style("✨").green());
 }
 
+/// 显示每个工具实际加载的 skills，被遮蔽的同名副本标注生效副本的位置
+pub fn display_resolution(tool_resolutions: &[ToolResolution]) {
+    if tool_resolutions.is_empty() {
+        println!("{}", style("未找到任何 skills").yellow());
+        return;
+    }
+
+    println!();
+    for tool_resolution in tool_resolutions {
+        let shadowed_label = if tool_resolution.shadowed_count > 0 {
+            style(format!("，{} 个被遮蔽", tool_resolution.shadowed_count)).yellow()
+        } else {
+            style(String::new())
+        };
+        println!(
+            "{} {}  {}{}",
+            style("🧭").cyan(),
+            style(&tool_resolution.tool).cyan().bold(),
+            style(format!("{} 个生效", tool_resolution.effective_count)).green(),
+            shadowed_label
+        );
+        for resolved_skill in &tool_resolution.skills {
+            let scope_label = format!("[{}]", resolved_skill.scope.label());
+            if resolved_skill.effective {
+                println!(
+                    "   {} {:<28} {}  {}",
+                    style("✓").green(),
+                    style(&resolved_skill.name).white(),
+                    style(scope_label).cyan(),
+                    style(resolved_skill.path.display()).dim()
+                );
+            } else {
+                println!(
+                    "   {} {:<28} {}  {}",
+                    style("✗").dim(),
+                    style(&resolved_skill.name).dim().strikethrough(),
+                    style(scope_label).dim(),
+                    style(resolved_skill.path.display()).dim()
+                );
+                if let Some(effective_path) = &resolved_skill.shadowed_by {
+                    println!(
+                        "     {}",
+                        style(format!("被 {} 遮蔽", effective_path.display())).yellow()
+                    );
+                }
+            }
+        }
+        println!();
+    }
+}
+
+/// 显示监视开始信息
+pub fn show_watching_message(root_count: usize, skill_count: usize, pending_root_count: usize) {
+    println!(
+        "\n{} 正在监视 {} 个目录（{} 个 skills），另有 {} 个目录出现后自动加入，按 Ctrl+C 退出\n",
+        style("👀").cyan(),
+        style(root_count).green(),
+        style(skill_count).green(),
+        style(pending_root_count).dim()
+    );
+}
+
+/// 显示一条监视事件
+pub fn display_watch_event(watch_event: &WatchEvent) {
+    let (marker, label) = match watch_event.kind {
+        WatchEventKind::RootAdded => (style("◎").cyan(), "新目录"),
+        WatchEventKind::Added => (style("+").green(), "新增"),
+        WatchEventKind::Modified => (style("~").yellow(), "修改"),
+        WatchEventKind::Removed => (style("-").red(), "删除"),
+    };
+    match &watch_event.name {
+        Some(name) => println!(
+            "{} {}  {} > {}  {}",
+            marker,
+            label,
+            style(&watch_event.tool).cyan(),
+            style(name).white().bold(),
+            style(watch_event.path.display()).dim()
+        ),
+        None => println!(
+            "{} {}  {}  {}",
+            marker,
+            label,
+            style(&watch_event.tool).cyan(),
+            style(watch_event.path.display()).dim()
+        ),
+    }
+}
+
+/// 显示同步计划：源副本以及每份目标副本将发生的文件变化
+pub fn display_sync_plan(source_skill: &Skill, sync_outcomes: &[SyncOutcome], delete_mode: &DeleteMode) {
+    println!(
+        "\n{} 同步 {}，源: {}\n   {}\n",
+        style("🔄").cyan(),
+        style(&source_skill.name).white().bold(),
+        style(&source_skill.tool).cyan(),
+        style(source_skill.path.display()).dim()
+    );
+
+    for sync_outcome in sync_outcomes {
+        let copy_label = format!(
+            "{} > {}  {}",
+            style(&sync_outcome.tool).cyan(),
+            style(&sync_outcome.name).white(),
+            style(sync_outcome.path.display()).dim()
+        );
+        match sync_outcome.status {
+            SyncStatus::UpToDate => println!("   {} {}  {}", style("✓").green(), copy_label, style("已一致").dim()),
+            SyncStatus::Failed => println!(
+                "   {} {}  {}",
+                style("✗").red(),
+                copy_label,
+                style(sync_outcome.message.as_deref().unwrap_or_default()).red()
+            ),
+            SyncStatus::Planned | SyncStatus::Synced => {
+                println!("   {} {}", style("•").yellow(), copy_label);
+                display_file_diff(&sync_outcome.diff);
+            }
+        }
+    }
+
+    if sync_outcomes.iter().any(|sync_outcome| sync_outcome.status == SyncStatus::Planned) {
+        let old_content_label = if delete_mode.is_permanent() {
+            style("旧内容将被永久删除").red()
+        } else {
+            style("旧内容将移入回收站").dim()
+        };
+        println!("\n   {}", old_content_label);
+    }
+    println!();
+}
+
+/// 显示同步结果
+pub fn display_sync_outcomes(sync_outcomes: &[SyncOutcome]) {
+    for sync_outcome in sync_outcomes {
+        let copy_label = format!("{} > {}", style(&sync_outcome.tool).cyan(), style(&sync_outcome.name).white());
+        match sync_outcome.status {
+            SyncStatus::Synced => println!(
+                "{} 已同步 {}{}",
+                style("✓").green(),
+                copy_label,
+                match &sync_outcome.trash_id {
+                    Some(trash_id) => format!("  {}", style(format!("(原内容 ID: {})", trash_id)).dim()),
+                    None => String::new(),
+                }
+            ),
+            SyncStatus::Failed => println!(
+                "{} 同步失败 {} - {}",
+                style("✗").red(),
+                copy_label,
+                style(sync_outcome.message.as_deref().unwrap_or_default()).red()
+            ),
+            SyncStatus::UpToDate | SyncStatus::Planned => {}
+        }
+    }
+    if !sync_outcomes.iter().any(|sync_outcome| sync_outcome.status == SyncStatus::Synced) {
+        println!("{} 所有副本均已与源一致", style("✓").green());
+    }
+}
+
+/// 显示 `dupes` 报告
+pub fn display_dupes(dupe_report: &DupeReport) {
+    if dupe_report.is_empty() {
+        println!("\n{} 未发现重复或分叉的 skills\n", style("✓").green());
+        return;
+    }
+
+    if !dupe_report.identical.is_empty() {
+        println!(
+            "\n{} {} 个 skill 在多个位置存在完全相同的副本:\n",
+            style("≡").green(),
+            style(dupe_report.identical.len()).green().bold()
+        );
+        for identical_group in &dupe_report.identical {
+            println!(
+                "  {}  {}",
+                style(&identical_group.name).white().bold(),
+                style(short_hash(&identical_group.content_hash)).dim()
+            );
+            for copy in &identical_group.copies {
+                display_skill_copy(copy);
+            }
+        }
+    }
+
+    if !dupe_report.diverged.is_empty() {
+        println!(
+            "\n{} {} 个同名 skill 的内容已分叉:\n",
+            style("≠").yellow(),
+            style(dupe_report.diverged.len()).yellow().bold()
+        );
+        for diverged_group in &dupe_report.diverged {
+            println!(
+                "  {}  {}",
+                style(&diverged_group.name).white().bold(),
+                style(format!("{} 个版本，以第一份为基准", diverged_group.variant_count)).dim()
+            );
+            display_skill_copy(&diverged_group.reference);
+            for diverged_copy in &diverged_group.copies {
+                display_skill_copy(&diverged_copy.copy);
+                display_file_diff(&diverged_copy.diff);
+            }
+        }
+    }
+
+    if !dupe_report.renamed.is_empty() {
+        println!(
+            "\n{} {} 组名称不同但内容相同的 skills:\n",
+            style("≈").cyan(),
+            style(dupe_report.renamed.len()).cyan().bold()
+        );
+        for renamed_group in &dupe_report.renamed {
+            println!("  {}", style(short_hash(&renamed_group.content_hash)).dim());
+            for copy in &renamed_group.copies {
+                display_skill_copy(copy);
+            }
+        }
+    }
+    println!();
+}
+
+fn display_skill_copy(copy: &SkillCopy) {
+    println!(
+        "     {} {} {}  {}",
+        style(&copy.tool).cyan(),
+        style(">").dim(),
+        style(&copy.name).white(),
+        style(copy.path.display()).dim().italic()
+    );
+}
+
+/// 显示文件级差异摘要
+pub fn display_file_diff(file_diff: &FileDiff) {
+    if file_diff.is_empty() {
+        println!("       {}", style("与基准相同").dim());
+        return;
+    }
+    for relative_path in &file_diff.added {
+        println!("       {} {}", style("+").green(), relative_path);
+    }
+    for relative_path in &file_diff.removed {
+        println!("       {} {}", style("-").red(), relative_path);
+    }
+    for relative_path in &file_diff.modified {
+        println!("       {} {}", style("~").yellow(), relative_path);
+    }
+}
+
+fn short_hash(content_hash: &str) -> &str {
+    &content_hash[..content_hash.len().min(12)]
+}
+
 #[cfg(test)]
 mod tests {
     use super::truncate_description;
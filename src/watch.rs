@@ -0,0 +1,296 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+
+use crate::scanner::{RootKind, Scanner};
+use crate::skill::Skill;
+
+/// 收到第一个文件系统通知后继续收集的时间，合并同一次操作产生的多个通知
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 监视事件类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WatchEventKind {
+    /// 之前不存在的扫描根目录出现
+    RootAdded,
+    /// 新增 skill
+    Added,
+    /// skill 内容或链接指向发生变化
+    Modified,
+    /// skill 被删除
+    Removed,
+}
+
+/// 一条监视事件
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchEvent {
+    pub kind: WatchEventKind,
+    pub tool: String,
+    pub path: PathBuf,
+    /// skill 名称；`root-added` 事件没有该字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// 扫描根目录类别；仅 `root-added` 事件有该字段
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_kind: Option<RootKind>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+impl WatchEvent {
+    fn for_skill(kind: WatchEventKind, skill: &Skill) -> Self {
+        Self {
+            kind,
+            tool: skill.tool.clone(),
+            path: skill.path.clone(),
+            name: Some(skill.name.clone()),
+            root_kind: None,
+            content_hash: skill.content_hash.clone(),
+        }
+    }
+}
+
+/// 比较前后两次扫描结果：先按新结果的顺序列出新增与修改，再按旧结果的顺序列出删除
+pub fn diff_skills(previous_skills: &[Skill], current_skills: &[Skill]) -> Vec<WatchEvent> {
+    let previous_skills_by_key: HashMap<(&str, &Path), &Skill> = previous_skills
+        .iter()
+        .map(|skill| ((skill.tool.as_str(), skill.path.as_path()), skill))
+        .collect();
+    let current_skills_by_key: HashMap<(&str, &Path), &Skill> = current_skills
+        .iter()
+        .map(|skill| ((skill.tool.as_str(), skill.path.as_path()), skill))
+        .collect();
+
+    let mut events = Vec::new();
+    for skill in current_skills {
+        match previous_skills_by_key.get(&(skill.tool.as_str(), skill.path.as_path())) {
+            None => events.push(WatchEvent::for_skill(WatchEventKind::Added, skill)),
+            Some(previous_skill) => {
+                if previous_skill.content_hash != skill.content_hash
+                    || previous_skill.link_target != skill.link_target
+                {
+                    events.push(WatchEvent::for_skill(WatchEventKind::Modified, skill));
+                }
+            }
+        }
+    }
+    for skill in previous_skills {
+        if !current_skills_by_key.contains_key(&(skill.tool.as_str(), skill.path.as_path())) {
+            events.push(WatchEvent::for_skill(WatchEventKind::Removed, skill));
+        }
+    }
+    events
+}
+
+/// 监视扫描根目录，文件系统变化时重新扫描并报告 skill 的增删改
+///
+/// 已存在的扫描根目录与链接指向的目录递归监视；尚不存在的候选根目录监视其最近的已存在祖先目录，
+/// 出现后自动加入扫描。
+pub struct SkillWatcher {
+    scanner: Scanner,
+    watcher: RecommendedWatcher,
+    receiver: mpsc::Receiver<notify::Result<notify::Event>>,
+    watched_paths: BTreeMap<PathBuf, RecursiveMode>,
+    skills: Vec<Skill>,
+}
+
+impl SkillWatcher {
+    /// 开始监视，并完成一次初始扫描
+    pub fn new(scanner: Scanner) -> crate::Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let watcher = notify::recommended_watcher(sender)?;
+        let skills = scanner.scan();
+        let mut skill_watcher = Self {
+            scanner,
+            watcher,
+            receiver,
+            watched_paths: BTreeMap::new(),
+            skills,
+        };
+        skill_watcher.update_watches()?;
+        Ok(skill_watcher)
+    }
+
+    /// 当前的扫描结果
+    pub fn skills(&self) -> &[Skill] {
+        &self.skills
+    }
+
+    /// 使用中的扫描器
+    pub fn scanner(&self) -> &Scanner {
+        &self.scanner
+    }
+
+    /// 等待文件系统变化并返回由此产生的事件；超时或变化不影响任何 skill 时返回空列表
+    pub fn poll_events(&mut self, timeout: Duration) -> crate::Result<Vec<WatchEvent>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining_time = deadline.saturating_duration_since(Instant::now());
+            let first_notification = match self.receiver.recv_timeout(remaining_time) {
+                Ok(notification) => notification,
+                Err(RecvTimeoutError::Timeout) => return Ok(Vec::new()),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(notify::Error::generic("文件系统通知通道已关闭").into())
+                }
+            };
+            let mut is_relevant = self.is_relevant(&first_notification);
+            while let Ok(notification) = self.receiver.recv_timeout(DEBOUNCE_INTERVAL) {
+                is_relevant = is_relevant || self.is_relevant(&notification);
+            }
+            if is_relevant {
+                return self.rescan();
+            }
+        }
+    }
+
+    /// 重新扫描并与上次结果比较
+    fn rescan(&mut self) -> crate::Result<Vec<WatchEvent>> {
+        let mut events: Vec<WatchEvent> = self
+            .scanner
+            .refresh_roots()
+            .into_iter()
+            .map(|scan_root| WatchEvent {
+                kind: WatchEventKind::RootAdded,
+                tool: scan_root.tool_name,
+                path: scan_root.path,
+                name: None,
+                root_kind: Some(scan_root.kind),
+                content_hash: None,
+            })
+            .collect();
+        let current_skills = self.scanner.scan();
+        events.extend(diff_skills(&self.skills, &current_skills));
+        self.skills = current_skills;
+        self.update_watches()?;
+        Ok(events)
+    }
+
+    /// 按当前的扫描根目录与 skills 调整监视列表
+    fn update_watches(&mut self) -> crate::Result<()> {
+        let mut desired_paths: BTreeMap<PathBuf, RecursiveMode> = BTreeMap::new();
+        let candidate_root_paths = self
+            .scanner
+            .roots()
+            .iter()
+            .chain(self.scanner.missing_roots())
+            .map(|scan_root| scan_root.path.as_path());
+        for root_path in candidate_root_paths {
+            if root_path.is_dir() {
+                desired_paths.insert(root_path.to_path_buf(), RecursiveMode::Recursive);
+            } else if let Some(ancestor_path) = root_path.ancestors().skip(1).find(|path| path.is_dir()) {
+                desired_paths
+                    .entry(ancestor_path.to_path_buf())
+                    .or_insert(RecursiveMode::NonRecursive);
+            }
+        }
+        // 递归监视不会跟随链接，链接指向的目录需要单独监视
+        for skill in &self.skills {
+            if skill.link_target.is_some() {
+                if let Ok(real_path) = fs::canonicalize(&skill.path) {
+                    desired_paths.insert(real_path, RecursiveMode::Recursive);
+                }
+            }
+        }
+
+        let stale_paths: Vec<PathBuf> = self
+            .watched_paths
+            .iter()
+            .filter(|(path, mode)| desired_paths.get(*path) != Some(*mode))
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in stale_paths {
+            // 目录已被删除时监视已自动失效，忽略错误
+            let _ = self.watcher.unwatch(&path);
+            self.watched_paths.remove(&path);
+        }
+        for (path, mode) in desired_paths {
+            if self.watched_paths.contains_key(&path) {
+                continue;
+            }
+            match self.watcher.watch(&path, mode) {
+                Ok(()) => {
+                    self.watched_paths.insert(path, mode);
+                }
+                // 检查与监视之间目录被删除，下次变化时再处理
+                Err(e) if matches!(e.kind, notify::ErrorKind::PathNotFound) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// 通知是否可能影响扫描结果：位于递归监视的目录中，或是某个扫描根目录的祖先
+    fn is_relevant(&self, notification: &notify::Result<notify::Event>) -> bool {
+        let Ok(event) = notification else {
+            // 通知队列溢出等错误时无法确定范围，重新扫描
+            return true;
+        };
+        if event.paths.is_empty() {
+            return true;
+        }
+        event.paths.iter().any(|event_path| {
+            self.watched_paths.iter().any(|(watched_path, mode)| {
+                *mode == RecursiveMode::Recursive && event_path.starts_with(watched_path)
+            }) || self
+                .scanner
+                .roots()
+                .iter()
+                .chain(self.scanner.missing_roots())
+                .any(|scan_root| scan_root.path.starts_with(event_path))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+
+    /// 等待直到收到事件，避免文件系统通知延迟导致测试不稳定
+    fn wait_for_events(skill_watcher: &mut SkillWatcher) -> Vec<WatchEvent> {
+        for _ in 0..20 {
+            let events = skill_watcher
+                .poll_events(Duration::from_millis(500))
+                .expect("failed to poll events");
+            if !events.is_empty() {
+                return events;
+            }
+        }
+        Vec::new()
+    }
+
+    #[test]
+    fn watcher_reports_added_modified_and_removed_skills() {
+        let temp_directory_path = create_temp_directory("watch");
+        let skill_directory_path = temp_directory_path.join("pdf");
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(temp_directory_path.clone());
+        let mut skill_watcher = SkillWatcher::new(scanner).expect("failed to start watcher");
+        assert!(skill_watcher.skills().is_empty());
+
+        fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+        fs::write(skill_directory_path.join("SKILL.md"), "---\nname: pdf\n---\nv1").expect("failed to write SKILL.md");
+        let events = wait_for_events(&mut skill_watcher);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WatchEventKind::Added);
+        assert_eq!(events[0].name.as_deref(), Some("pdf"));
+
+        fs::write(skill_directory_path.join("SKILL.md"), "---\nname: pdf\n---\nv2").expect("failed to write SKILL.md");
+        let events = wait_for_events(&mut skill_watcher);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WatchEventKind::Modified);
+
+        fs::remove_dir_all(&skill_directory_path).expect("failed to remove skill directory");
+        let events = wait_for_events(&mut skill_watcher);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, WatchEventKind::Removed);
+        assert_eq!(events[0].path, skill_directory_path);
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
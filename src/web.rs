@@ -0,0 +1,310 @@
+use std::io;
+use std::net::SocketAddr;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::artifact::ArtifactKind;
+use crate::audit;
+use crate::delete::SkillSelector;
+use crate::finding::Finding;
+use crate::lint;
+use crate::registry::ToolRegistry;
+use crate::scanner::Scanner;
+use crate::skill::{Skill, SkillRecord};
+
+/// `serve` 的默认端口
+pub const DEFAULT_PORT: u16 = 7777;
+
+/// 内嵌的只读仪表盘页面
+const DASHBOARD_HTML: &str = include_str!("dashboard.html");
+
+/// 绑定到回环地址时接受的 `Host` 主机名，防止 DNS 重绑定让其他网页读取本机 skills
+const LOOPBACK_HOST_NAMES: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// 一个 HTTP 响应
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl HttpResponse {
+    fn json(status: u16, body: &impl Serialize) -> Self {
+        Self {
+            status,
+            content_type: "application/json; charset=utf-8",
+            body: serde_json::to_string_pretty(body).unwrap_or_default(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &json!({ "error": message }))
+    }
+}
+
+/// 查询参数：`tool`、`kind` 与关键字 `q`
+#[derive(Debug, Default)]
+struct SkillQuery {
+    tool: Option<String>,
+    kind: Option<String>,
+    keyword: Option<String>,
+}
+
+impl SkillQuery {
+    fn parse(query_string: &str) -> Self {
+        let mut skill_query = Self::default();
+        for pair in query_string.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = Some(decode_query_component(value)).filter(|value| !value.is_empty());
+            match key {
+                "tool" => skill_query.tool = value,
+                "kind" => skill_query.kind = value,
+                "q" => skill_query.keyword = value,
+                _ => {}
+            }
+        }
+        skill_query
+    }
+}
+
+/// HTTP API 与仪表盘页面，每个请求都重新扫描
+pub struct DashboardApi {
+    scanner: Scanner,
+    registry: ToolRegistry,
+}
+
+impl DashboardApi {
+    pub fn new(scanner: Scanner, registry: ToolRegistry) -> Self {
+        Self { scanner, registry }
+    }
+
+    /// 处理一个请求；只接受 GET 与 HEAD
+    pub fn handle(&self, method: &str, url: &str) -> HttpResponse {
+        if method != "GET" && method != "HEAD" {
+            return HttpResponse::error(405, "只支持 GET 请求");
+        }
+        let (path, query_string) = url.split_once('?').unwrap_or((url, ""));
+        let skill_query = SkillQuery::parse(query_string);
+        match path.trim_end_matches('/') {
+            "" | "/index.html" => HttpResponse {
+                status: 200,
+                content_type: "text/html; charset=utf-8",
+                body: DASHBOARD_HTML.to_string(),
+            },
+            "/api/skills" => self.list_skills(&skill_query),
+            "/api/tools" => HttpResponse::json(200, &json!({ "tools": self.registry.entries() })),
+            "/api/findings" => self.list_findings(&skill_query),
+            _ => match path.strip_prefix("/api/skills/") {
+                Some(skill_id) => self.get_skill(skill_id),
+                None => HttpResponse::error(404, "未找到"),
+            },
+        }
+    }
+
+    /// 扫描并按查询参数筛选
+    fn scan_filtered(&self, skill_query: &SkillQuery) -> Result<Vec<Skill>, HttpResponse> {
+        let tool_patterns: Vec<String> = skill_query.tool.iter().cloned().collect();
+        let selector = SkillSelector::new(&[], &tool_patterns, &[])
+            .map_err(|e| HttpResponse::error(400, &format!("无效的工具模式: {}", e)))?;
+        let kind = skill_query
+            .kind
+            .as_deref()
+            .map(|kind_label| {
+                ArtifactKind::from_str(kind_label, true)
+                    .map_err(|_| HttpResponse::error(400, &format!("未知类别: {}", kind_label)))
+            })
+            .transpose()?;
+        let keyword = skill_query.keyword.as_deref().map(str::to_lowercase);
+        Ok(self
+            .scanner
+            .scan()
+            .into_iter()
+            .filter(|skill| selector.matches(skill) && kind.is_none_or(|kind| skill.kind == kind))
+            .filter(|skill| {
+                keyword.as_deref().is_none_or(|keyword| {
+                    skill.qualified_name().to_lowercase().contains(keyword)
+                        || skill
+                            .description
+                            .as_deref()
+                            .is_some_and(|description| description.to_lowercase().contains(keyword))
+                })
+            })
+            .collect())
+    }
+
+    fn list_skills(&self, skill_query: &SkillQuery) -> HttpResponse {
+        match self.scan_filtered(skill_query) {
+            Ok(skills) => HttpResponse::json(
+                200,
+                &json!({ "skills": skills.iter().map(SkillRecord::new).collect::<Vec<_>>() }),
+            ),
+            Err(response) => response,
+        }
+    }
+
+    fn get_skill(&self, skill_id: &str) -> HttpResponse {
+        let skills = self.scanner.scan();
+        let Some(skill) = skills.iter().find(|skill| skill.id() == skill_id) else {
+            return HttpResponse::error(404, "未找到该 skill");
+        };
+        let mut skill_document = serde_json::to_value(SkillRecord::new(skill)).unwrap_or_default();
+        skill_document["content"] = std::fs::read_to_string(skill.main_file_path())
+            .map(serde_json::Value::String)
+            .unwrap_or_default();
+        skill_document["files"] = json!(skill.list_files());
+        HttpResponse::json(200, &skill_document)
+    }
+
+    /// lint 与 audit 的全部发现；只检查 skill 目录
+    fn list_findings(&self, skill_query: &SkillQuery) -> HttpResponse {
+        let skills: Vec<Skill> = match self.scan_filtered(skill_query) {
+            Ok(skills) => skills.into_iter().filter(|skill| skill.kind == ArtifactKind::Skill).collect(),
+            Err(response) => return response,
+        };
+        let mut findings: Vec<Finding> = lint::lint_skills(&skills);
+        findings.extend(audit::audit_skills(&skills, self.scanner.options()));
+        HttpResponse::json(200, &json!({ "findings": findings }))
+    }
+}
+
+/// 绑定到本地端口的 HTTP 服务器
+pub struct DashboardServer {
+    api: DashboardApi,
+    http_server: tiny_http::Server,
+    is_loopback: bool,
+}
+
+impl DashboardServer {
+    /// 绑定地址；端口被占用等情况返回错误
+    pub fn bind(api: DashboardApi, address: SocketAddr) -> crate::Result<Self> {
+        let http_server = tiny_http::Server::http(address).map_err(io::Error::other)?;
+        Ok(Self {
+            api,
+            http_server,
+            is_loopback: address.ip().is_loopback(),
+        })
+    }
+
+    /// 实际监听的地址（绑定端口 0 时由系统分配）
+    pub fn local_address(&self) -> Option<SocketAddr> {
+        self.http_server.server_addr().to_ip()
+    }
+
+    /// 依次处理请求，直到进程被中断
+    pub fn run(&self) -> crate::Result<()> {
+        for request in self.http_server.incoming_requests() {
+            let response = if self.is_loopback && !is_loopback_host(&request) {
+                HttpResponse::error(403, "只接受通过 localhost 访问")
+            } else {
+                self.api.handle(request.method().as_str(), request.url())
+            };
+            let content_type = tiny_http::Header::from_bytes("Content-Type", response.content_type)
+                .expect("content type header should be valid");
+            let http_response = tiny_http::Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(content_type);
+            // 客户端提前断开时忽略写入错误
+            let _ = request.respond(http_response);
+        }
+        Ok(())
+    }
+}
+
+/// 请求的 `Host` 是否指向本机回环地址
+fn is_loopback_host(request: &tiny_http::Request) -> bool {
+    let Some(host_header) = request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv("Host"))
+    else {
+        return false;
+    };
+    let host = host_header.value.as_str();
+    // 去掉端口：`[::1]:7777` 与 `localhost:7777`
+    let host_name = match host.rfind(':') {
+        Some(colon_index) if !host.ends_with(']') => &host[..colon_index],
+        _ => host,
+    };
+    LOOPBACK_HOST_NAMES
+        .iter()
+        .any(|loopback_host_name| host_name.eq_ignore_ascii_case(loopback_host_name))
+}
+
+/// 解码 `application/x-www-form-urlencoded` 的查询参数值
+fn decode_query_component(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded_bytes = Vec::with_capacity(bytes.len());
+    let mut byte_index = 0;
+    while byte_index < bytes.len() {
+        match bytes[byte_index] {
+            b'+' => decoded_bytes.push(b' '),
+            b'%' if byte_index + 2 < bytes.len() => {
+                let hex_digit = |byte: u8| (byte as char).to_digit(16);
+                match (hex_digit(bytes[byte_index + 1]), hex_digit(bytes[byte_index + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded_bytes.push((high * 16 + low) as u8);
+                        byte_index += 2;
+                    }
+                    _ => decoded_bytes.push(b'%'),
+                }
+            }
+            byte => decoded_bytes.push(byte),
+        }
+        byte_index += 1;
+    }
+    String::from_utf8_lossy(&decoded_bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::create_temp_directory;
+    use std::fs;
+
+    #[test]
+    fn api_routes_filter_and_return_skill_details() {
+        let temp_directory_path = create_temp_directory("web");
+        for (skill_name, description) in [("pdf", "处理 PDF 文件"), ("docx", "编辑 Word 文档")] {
+            let skill_directory_path = temp_directory_path.join(skill_name);
+            fs::create_dir_all(&skill_directory_path).expect("failed to create skill directory");
+            fs::write(
+                skill_directory_path.join("SKILL.md"),
+                format!("---\nname: {}\ndescription: {}\n---\n", skill_name, description),
+            )
+            .expect("failed to write SKILL.md");
+        }
+        let mut scanner = Scanner::empty();
+        scanner.add_custom_path(temp_directory_path.clone());
+        let dashboard_api = DashboardApi::new(scanner, ToolRegistry::builtin());
+        let parse_body = |response: &HttpResponse| -> serde_json::Value {
+            serde_json::from_str(&response.body).expect("response body should be JSON")
+        };
+
+        let index_response = dashboard_api.handle("GET", "/");
+        assert_eq!(index_response.status, 200);
+        assert!(index_response.content_type.starts_with("text/html"));
+
+        let list_response = dashboard_api.handle("GET", "/api/skills?q=%E5%A4%84%E7%90%86+pdf&kind=skill");
+        assert_eq!(list_response.status, 200);
+        let listed_skills = parse_body(&list_response)["skills"].as_array().cloned().unwrap();
+        assert_eq!(listed_skills.len(), 1);
+        assert_eq!(listed_skills[0]["name"], "pdf");
+
+        let skill_id = listed_skills[0]["id"].as_str().unwrap();
+        let detail_response = dashboard_api.handle("GET", &format!("/api/skills/{}", skill_id));
+        assert_eq!(detail_response.status, 200);
+        let skill_document = parse_body(&detail_response);
+        assert!(skill_document["content"].as_str().unwrap().contains("处理 PDF 文件"));
+        assert_eq!(skill_document["files"], json!(["SKILL.md"]));
+
+        assert_eq!(dashboard_api.handle("GET", "/api/skills/unknown").status, 404);
+        assert_eq!(dashboard_api.handle("GET", "/api/skills?kind=nope").status, 400);
+        assert_eq!(dashboard_api.handle("POST", "/api/skills").status, 405);
+        assert_eq!(dashboard_api.handle("GET", "/api/tools").status, 200);
+        assert!(parse_body(&dashboard_api.handle("GET", "/api/findings"))["findings"].is_array());
+
+        fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+    }
+}
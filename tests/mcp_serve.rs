@@ -0,0 +1,124 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use serde_json::{json, Value};
+
+#[path = "../src/test_support.rs"]
+mod test_support;
+
+use test_support::create_temp_directory;
+
+/// 以隔离的 HOME 运行 `skills-scanner --no-cache <args>`
+fn isolated_command(temp_directory_path: &Path, args: &[&str]) -> Command {
+    // 工作区目录带 .git，避免向上查找到测试机上真实的项目级目录
+    let workspace_directory_path = temp_directory_path.join("workspace");
+    fs::create_dir_all(workspace_directory_path.join(".git")).expect("failed to create workspace");
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_skills-scanner"));
+    command
+        .arg("--no-cache")
+        .args(args)
+        .current_dir(&workspace_directory_path)
+        .env("HOME", temp_directory_path.join("home"))
+        .env("XDG_CONFIG_HOME", temp_directory_path.join("config"))
+        .env("XDG_DATA_HOME", temp_directory_path.join("data"))
+        .env("XDG_CACHE_HOME", temp_directory_path.join("cache"));
+    command
+}
+
+/// 启动 `mcp-serve`，依次发送请求后关闭 stdin，返回按行解析的全部响应
+fn run_mcp_session(temp_directory_path: &Path, requests: &[Value]) -> Vec<Value> {
+    let mut child = isolated_command(temp_directory_path, &["mcp-serve"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to spawn skills-scanner");
+
+    let mut stdin = child.stdin.take().expect("stdin should be piped");
+    for request in requests {
+        writeln!(stdin, "{}", request).expect("failed to write request");
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output().expect("failed to wait for skills-scanner");
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .expect("stdout should be UTF-8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("every stdout line should be a JSON-RPC message"))
+        .collect()
+}
+
+#[test]
+fn mcp_serve_lists_gets_and_searches_skills_over_stdio() {
+    let temp_directory_path = create_temp_directory("mcp-serve");
+    let skill_directory_path = temp_directory_path.join("home").join(".claude").join("skills").join("pdf");
+    fs::create_dir_all(skill_directory_path.join("scripts")).expect("failed to create skill directory");
+    fs::write(
+        skill_directory_path.join("SKILL.md"),
+        "---\nname: pdf\ndescription: 提取 PDF 中的文本与表格\n---\n# PDF\n",
+    )
+    .expect("failed to write SKILL.md");
+    fs::write(skill_directory_path.join("scripts").join("extract.py"), "print('ok')\n").expect("failed to write script");
+
+    let responses = run_mcp_session(
+        &temp_directory_path,
+        &[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {
+                "protocolVersion": "2025-06-18",
+                "capabilities": {},
+                "clientInfo": {"name": "test-client", "version": "0"}
+            }}),
+            json!({"jsonrpc": "2.0", "method": "notifications/initialized"}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": {"name": "list_skills", "arguments": {}}}),
+            json!({"jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": {"name": "get_skill", "arguments": {"name": "pdf"}}}),
+            json!({"jsonrpc": "2.0", "id": 5, "method": "tools/call", "params": {"name": "search_skills", "arguments": {"query": "表格"}}}),
+            json!({"jsonrpc": "2.0", "id": 6, "method": "tools/call", "params": {"name": "get_skill", "arguments": {"name": "missing"}}}),
+            json!({"jsonrpc": "2.0", "id": 7, "method": "resources/list"}),
+        ],
+    );
+
+    // 通知没有响应，其余请求按顺序各有一条
+    let response_ids: Vec<i64> = responses.iter().map(|response| response["id"].as_i64().unwrap()).collect();
+    assert_eq!(response_ids, vec![1, 2, 3, 4, 5, 6, 7]);
+
+    assert_eq!(responses[0]["result"]["protocolVersion"], "2025-06-18");
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "skills-scanner");
+
+    let tool_names: Vec<&str> = responses[1]["result"]["tools"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tool| tool["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(tool_names, vec!["list_skills", "get_skill", "search_skills"]);
+
+    let listed_skills = responses[2]["result"]["structuredContent"]["skills"].as_array().unwrap();
+    assert_eq!(listed_skills.len(), 1);
+    assert_eq!(listed_skills[0]["name"], "pdf");
+    assert_eq!(listed_skills[0]["tool"], "Claude Code");
+
+    let skill_document = &responses[3]["result"]["structuredContent"];
+    assert_eq!(skill_document["id"], listed_skills[0]["id"]);
+    assert!(skill_document["content"].as_str().unwrap().contains("提取 PDF 中的文本与表格"));
+    assert_eq!(skill_document["files"], json!(["SKILL.md", "scripts/extract.py"]));
+
+    let found_skills = responses[4]["result"]["structuredContent"]["skills"].as_array().unwrap();
+    assert_eq!(found_skills.len(), 1);
+
+    assert_eq!(responses[5]["result"]["isError"], true);
+    assert_eq!(responses[6]["error"]["code"], -32601);
+
+    // CLI 的 JSON 输出与 MCP 使用同一表示，id 可以互相对应
+    let list_output = isolated_command(&temp_directory_path, &["--format", "json"])
+        .output()
+        .expect("failed to run skills-scanner --format json");
+    let list_document: Value = serde_json::from_slice(&list_output.stdout).expect("stdout should be JSON");
+    assert_eq!(list_document["skills"][0]["id"], listed_skills[0]["id"]);
+    assert_eq!(list_document["skills"][0]["root_kind"], listed_skills[0]["root_kind"]);
+
+    fs::remove_dir_all(&temp_directory_path).expect("failed to remove temp directory");
+}